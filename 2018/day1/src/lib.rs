@@ -0,0 +1,120 @@
+use aoc::Solution;
+use std::collections::HashSet;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+
+    fn parse(&self, input: &str) -> aoc::Result<Vec<i32>> {
+        aoc::parse::lines(input)
+    }
+
+    fn part1(&self, changes: &Vec<i32>) -> aoc::Result<aoc::Answer> {
+        Ok(changes.iter().sum::<i32>().into())
+    }
+
+    fn part2(&self, changes: &Vec<i32>) -> aoc::Result<aoc::Answer> {
+        Ok(functional(changes)?.1.into())
+    }
+}
+
+/// How many passes over `changes` after the first may find a repeated frequency. Each pass
+/// moves every frequency by the same drift, so once the passes have drifted further than the
+/// first pass spans, no frequency can come back to one seen before.
+fn passes(changes: &[i32]) -> aoc::Result<usize> {
+    if changes.is_empty() {
+        return Err(aoc::Error::ExecError(
+            "there are no frequency changes".to_string(),
+        ));
+    }
+
+    let (mut freq, mut min, mut max) = (0i64, 0i64, 0i64);
+    for &change in changes {
+        freq += i64::from(change);
+        min = min.min(freq);
+        max = max.max(freq);
+    }
+    Ok(match freq {
+        0 => 1,
+        drift => ((max - min) / drift.abs()) as usize + 2,
+    })
+}
+
+fn never_repeats() -> aoc::Error {
+    aoc::Error::ExecError("the frequency never repeats".to_string())
+}
+
+/// Final frequency and first repeated frequency.
+pub fn imperative(changes: &[i32]) -> aoc::Result<(i32, i32)> {
+    let passes = passes(changes)?;
+    let mut freq: i32 = 0;
+    let mut freq_hist: HashSet<i32> = HashSet::new();
+
+    freq_hist.insert(freq);
+
+    for change in changes {
+        freq += change;
+        freq_hist.insert(freq);
+    }
+
+    let final_freq = freq;
+
+    for _ in 0..passes {
+        for change in changes {
+            freq += change;
+            if !freq_hist.insert(freq) {
+                return Ok((final_freq, freq));
+            }
+        }
+    }
+
+    Err(never_repeats())
+}
+
+/// Final frequency and first repeated frequency.
+pub fn functional(changes: &[i32]) -> aoc::Result<(i32, i32)> {
+    let passes = passes(changes)?;
+    let mut freq_hist: HashSet<i32> = HashSet::new();
+
+    let final_freq = changes.iter().fold(0, |acc, &x| {
+        let freq = acc + x;
+        freq_hist.insert(freq);
+        freq
+    });
+
+    let repeated_freq = changes
+        .iter()
+        .cycle()
+        .scan(final_freq, |state, &x| {
+            *state += x;
+            Some(*state)
+        })
+        .take(changes.len() * passes)
+        .find(|x| !freq_hist.insert(*x))
+        .ok_or_else(never_repeats)?;
+
+    Ok((final_freq, repeated_freq))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frequencies() {
+        let changes = vec![3, 3, 4, -2, -4];
+        assert_eq!(imperative(&changes).unwrap(), (4, 10));
+        assert_eq!(functional(&changes).unwrap(), (4, 10));
+
+        let changes = vec![-6, 3, 8, 5, -6];
+        assert_eq!(imperative(&changes).unwrap(), (4, 5));
+        assert_eq!(functional(&changes).unwrap(), (4, 5));
+
+        assert_eq!(Day1.part1(&vec![1]).unwrap(), aoc::Answer::from(1));
+        for changes in &[vec![1], vec![], vec![5, -2]] {
+            assert!(imperative(changes).is_err());
+            assert!(functional(changes).is_err());
+        }
+    }
+}
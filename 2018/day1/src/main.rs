@@ -1,77 +1,18 @@
-use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Result};
+use aoc::Solution;
+use aoc2018_day1::Day1;
 
-fn main() -> Result<()> {
-    let changes = read_change_list()?;
-    println!("=====Imperative=====");
-    imperative(&changes);
-    println!("=====Functional======");
-    functional(&changes);
-
-    Ok(())
-}
-
-fn read_change_list() -> Result<Vec<i32>> {
-    let input = File::open("input")?;
-    let buffered = BufReader::new(input);
-
-    let changes: Vec<_> = buffered
-        .lines()
-        .filter_map(|line| line.ok())
-        .map(|x| x.parse::<i32>())
-        .filter_map(|x| x.ok())
-        .collect();
-
-    Ok(changes)
-}
-
-fn imperative(changes: &Vec<i32>) {
-    let mut freq: i32 = 0;
-    let mut freq_hist: HashSet<i32> = HashSet::new();
-
-    freq_hist.insert(freq);
-
-    for change in changes {
-        freq += change;
-        freq_hist.insert(freq);
-    }
-
-    println!("Final frequency: {}", freq);
-
-    'outer: loop {
-        for change in changes {
-            freq += change;
-            if !freq_hist.insert(freq) {
-                println!("Repeated frequency: {}", freq);
-                break 'outer;
-            }
-        }
-    }
-}
-
-fn functional(changes: &Vec<i32>) {
-    let mut freq_hist: HashSet<i32> = HashSet::new();
-
-    let final_freq = changes.iter().fold(0, |acc, &x| {
-        let freq = acc + x;
-        freq_hist.insert(freq);
-        freq
-    });
+fn main() -> aoc::Result<()> {
+    let changes = Day1.parse(&aoc::input::read(2018, 1)?)?;
 
+    println!("=====Imperative=====");
+    let (final_freq, repeated_freq) = aoc2018_day1::imperative(&changes)?;
     println!("Final frequency: {}", final_freq);
+    println!("Repeated frequency: {}", repeated_freq);
 
-    let repeated_freq = changes
-        .iter()
-        .cycle()
-        .scan(final_freq, |state, &x| {
-            *state = *state + x;
-            Some(*state)
-        })
-        .skip_while(|x| freq_hist.insert(*x))
-        .take(1)
-        .next()
-        .unwrap();
+    println!("=====Functional======");
+    let (final_freq, repeated_freq) = aoc2018_day1::functional(&changes)?;
+    println!("Final frequency: {}", final_freq);
+    println!("Repeated frequency: {}", repeated_freq);
 
-    println!("Repeated frequency: {}", repeated_freq)
+    Ok(())
 }
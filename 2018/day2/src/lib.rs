@@ -0,0 +1,80 @@
+use aoc::Solution;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+struct IdLetters(u32, u32);
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> aoc::Result<Vec<String>> {
+        Ok(input
+            .lines()
+            .map(|line| line.to_string())
+            .sorted()
+            .collect())
+    }
+
+    fn part1(&self, id_list: &Vec<String>) -> aoc::Result<aoc::Answer> {
+        Ok(checksum(id_list).into())
+    }
+
+    fn part2(&self, id_list: &Vec<String>) -> aoc::Result<aoc::Answer> {
+        common_id_chars(id_list)
+            .map(aoc::Answer::from)
+            .ok_or_else(|| aoc::Error::ExecError("no two IDs differ by one character".to_string()))
+    }
+}
+
+pub fn checksum(id_list: &[String]) -> u32 {
+    let letter_sums = id_list
+        .iter()
+        .map(|id| count_id_letters(id))
+        .fold(IdLetters(0, 0), |acc, v| {
+            IdLetters(acc.0 + v.0, acc.1 + v.1)
+        });
+
+    letter_sums.0 * letter_sums.1
+}
+
+fn count_id_letters(id: &str) -> IdLetters {
+    id.chars()
+        .fold(HashMap::new(), |mut acc, c| {
+            let count = acc.entry(c).or_insert(0);
+            *count += 1;
+            acc
+        })
+        .values()
+        .fold(IdLetters(0, 0), |acc, count| match count {
+            2 => IdLetters(1, acc.1),
+            3 => IdLetters(acc.0, 1),
+            _ => acc,
+        })
+}
+
+/// The letters shared by the two IDs that differ by a single character.
+pub fn common_id_chars(ids: &[String]) -> Option<String> {
+    for n in 0..ids.len() {
+        for i in (n + 1)..ids.len() {
+            match diff_size(&ids[n], &ids[i]) {
+                0 | 1 => return Some(common_chars(&ids[n], &ids[i])),
+                _ => continue,
+            }
+        }
+    }
+
+    None
+}
+
+fn diff_size(id1: &str, id2: &str) -> usize {
+    id1.chars().zip(id2.chars()).filter(|(a, b)| a != b).count()
+}
+
+fn common_chars(id1: &str, id2: &str) -> String {
+    id1.chars()
+        .zip(id2.chars())
+        .filter_map(|(a, b)| if a == b { Some(a) } else { None })
+        .collect()
+}
@@ -0,0 +1,41 @@
+use aoc::Solution;
+
+mod cloth;
+mod parse;
+
+pub use parse::Claim;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Claim>;
+
+    fn parse(&self, input: &str) -> aoc::Result<Vec<Claim>> {
+        aoc::parse::records(input, parse::claim)
+    }
+
+    fn part1(&self, claims: &Vec<Claim>) -> aoc::Result<aoc::Answer> {
+        Ok(cut(claims).find_overlaps().into())
+    }
+
+    fn part2(&self, claims: &Vec<Claim>) -> aoc::Result<aoc::Answer> {
+        non_overlapping_claim(claims)
+            .map(|claim| claim.id.into())
+            .ok_or_else(|| aoc::Error::ExecError("every claim overlaps another".to_string()))
+    }
+}
+
+fn cut(claims: &[Claim]) -> cloth::Cloth {
+    let mut c = cloth::Cloth::of_size(1000, 1000);
+    claims
+        .iter()
+        .for_each(|x| c.add_claim(x.left, x.top, x.width, x.height));
+    c
+}
+
+pub fn non_overlapping_claim(claims: &[Claim]) -> Option<&Claim> {
+    let c = cut(claims);
+    claims
+        .iter()
+        .find(|claim| c.has_overlapped(claim.left, claim.top, claim.width, claim.height))
+}
@@ -1,4 +1,10 @@
-use nom::types::CompleteStr;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, space0},
+    combinator::{all_consuming, map_res},
+    sequence::{delimited, tuple},
+    IResult,
+};
 
 #[derive(Debug, PartialEq)]
 pub struct Claim {
@@ -9,42 +15,53 @@ pub struct Claim {
     pub height: u32,
 }
 
-fn to_u32(input: CompleteStr) -> Result<u32, std::num::ParseIntError> {
-    u32::from_str_radix(&input, 10)
+fn number(input: &str) -> IResult<&str, u32> {
+    map_res(digit1, |s: &str| s.parse::<u32>())(input)
 }
 
-fn is_digit(c: char) -> bool {
-    c.is_digit(10)
+fn symbol<'a>(s: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
+    delimited(space0, tag(s), space0)
 }
 
-named!(number<CompleteStr, u32>, map_res!(take_while!(is_digit), to_u32));
+fn parse_claim(input: &str) -> IResult<&str, Claim> {
+    let (input, (_, id, _, left, _, top, _, width, _, height)) = tuple((
+        symbol("#"),
+        number,
+        symbol("@"),
+        number,
+        symbol(","),
+        number,
+        symbol(":"),
+        number,
+        symbol("x"),
+        number,
+    ))(input)?;
 
-named!(parse_claim<CompleteStr, Claim>,
-    ws!(do_parse!(
-       tag!("#") >>
-       id: number >>
-       tag!("@") >>
-       left: number >>
-       tag!(",") >>
-       top: number >>
-       tag!(":") >>
-       width: number >>
-       tag!("x") >>
-       height: number >>
-       (Claim{ id , left, top, width, height })
+    Ok((
+        input,
+        Claim {
+            id,
+            left,
+            top,
+            width,
+            height,
+        },
     ))
-);
+}
 
-pub fn claim(input: &str) -> Option<Claim> {
-    parse_claim(CompleteStr(input)).ok().map(|x| x.1)
+pub fn claim(input: &str) -> aoc::Result<Claim> {
+    let trimmed = input.trim();
+    all_consuming(parse_claim)(trimmed)
+        .map(|(_, parsed)| parsed)
+        .map_err(|err| aoc::Error::from(err).locate(input, trimmed))
 }
 
 #[test]
 fn test_parse_claim() {
     assert_eq!(
-        parse_claim(CompleteStr("#123 @ 3,2: 5x4")),
+        parse_claim("#123 @ 3,2: 5x4"),
         Ok((
-            CompleteStr(""),
+            "",
             Claim {
                 id: 123,
                 left: 3,
@@ -53,5 +70,6 @@ fn test_parse_claim() {
                 height: 4,
             }
         ))
-    )
+    );
+    assert!(claim("#123 @ 3,2: 5y4").is_err());
 }
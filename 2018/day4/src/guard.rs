@@ -26,15 +26,14 @@ impl Log {
     }
 }
 
+#[derive(Default)]
 pub struct Schedule {
     schedule: HashMap<u32, Log>,
 }
 
 impl Schedule {
     pub fn new() -> Schedule {
-        Schedule {
-            schedule: HashMap::new(),
-        }
+        Schedule::default()
     }
 
     pub fn mark_asleep(&mut self, guard: u32, from: u8, to: u8) {
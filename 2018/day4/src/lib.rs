@@ -0,0 +1,63 @@
+use aoc::Solution;
+
+mod guard;
+mod parse;
+
+pub use guard::Schedule;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Schedule;
+
+    fn parse(&self, input: &str) -> aoc::Result<Schedule> {
+        let mut entries = aoc::parse::records(input, parse::entry)?;
+
+        entries.sort_by(|a, b| {
+            a.year
+                .cmp(&b.year)
+                .then(a.month.cmp(&b.month))
+                .then(a.day.cmp(&b.day))
+                .then(a.hour.cmp(&b.hour))
+                .then(a.minute.cmp(&b.minute))
+        });
+
+        Ok(build_schedule(entries))
+    }
+
+    fn part1(&self, schedule: &Schedule) -> aoc::Result<aoc::Answer> {
+        schedule
+            .longest_sleeper_and_minute()
+            .map(|(guard, minute)| (guard * minute as u32).into())
+            .ok_or_else(|| aoc::Error::ExecError("no guard ever fell asleep".to_string()))
+    }
+
+    fn part2(&self, schedule: &Schedule) -> aoc::Result<aoc::Answer> {
+        schedule
+            .habitual_sleeper_and_minute()
+            .map(|(guard, minute)| (guard * minute as u32).into())
+            .ok_or_else(|| aoc::Error::ExecError("no guard ever fell asleep".to_string()))
+    }
+}
+
+fn build_schedule(entries: Vec<parse::Entry>) -> Schedule {
+    let mut schedule = Schedule::new();
+
+    let mut current_guard: u32 = 0;
+    let mut sleep_start: u8 = 0;
+
+    for e in entries {
+        match e.event {
+            parse::Event::StartShift(id) => current_guard = id,
+            parse::Event::FallAsleep => {
+                sleep_start = e.minute;
+            }
+            parse::Event::WakeUp => {
+                let sleep_end = e.minute;
+                schedule.mark_asleep(current_guard, sleep_start, sleep_end);
+            }
+        }
+    }
+
+    schedule
+}
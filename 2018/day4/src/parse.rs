@@ -1,4 +1,11 @@
-use nom::types::CompleteStr;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1, space1},
+    combinator::{all_consuming, map, map_res},
+    sequence::{delimited, tuple},
+    IResult,
+};
 use std::str::FromStr;
 
 #[derive(Debug, Eq, PartialEq)]
@@ -18,71 +25,60 @@ pub struct Entry {
     pub event: Event,
 }
 
-fn to_uint<T>(input: CompleteStr) -> Result<T, T::Err>
-where
-    T: FromStr,
-{
-    input.parse::<T>()
+fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, |s: &str| s.parse::<T>())(input)
 }
 
-fn is_digit(c: char) -> bool {
-    c.is_digit(10)
+fn event_start_shift(input: &str) -> IResult<&str, Event> {
+    map(
+        delimited(tag("Guard #"), number, tag(" begins shift")),
+        Event::StartShift,
+    )(input)
 }
 
-named!(num_u8<CompleteStr, u8>, map_res!(take_while!(is_digit), to_uint::<u8>));
-
-named!(num_u16<CompleteStr, u16>, map_res!(take_while!(is_digit), to_uint::<u16>));
-
-named!(num_u32<CompleteStr, u32>, map_res!(take_while!(is_digit), to_uint::<u32>));
-
-named!(event_start_shift<CompleteStr, Event>,
-       ws!(do_parse!(
-            tag!("Guard") >>
-            tag!("#") >>
-            id: num_u32 >>
-            tag!("begins") >>
-            tag!("shift") >>
-            (Event::StartShift(id))
-       ))
-);
-
-named!(event_fall_asleep<CompleteStr, Event>,
-       ws!(do_parse!(
-               tag!("falls") >>
-               tag!("asleep") >>
-               (Event::FallAsleep)
-       ))
-);
+fn event_fall_asleep(input: &str) -> IResult<&str, Event> {
+    map(tag("falls asleep"), |_| Event::FallAsleep)(input)
+}
 
-named!(event_wake_up<CompleteStr, Event>,
-       ws!(do_parse!(
-               tag!("wakes") >>
-               tag!("up") >>
-               (Event::WakeUp)
-       ))
-);
+fn event_wake_up(input: &str) -> IResult<&str, Event> {
+    map(tag("wakes up"), |_| Event::WakeUp)(input)
+}
 
-named!(parse_entry<CompleteStr, Entry>,
-       do_parse!(
-               tag!("[") >>
-               year: num_u16 >>
-               tag!("-") >>
-               month: num_u8 >>
-               tag!("-") >>
-               day: num_u8 >>
-               tag!(" ") >>
-               hour: num_u8 >>
-               tag!(":") >>
-               minute: num_u8 >>
-               tag!("]") >>
-               tag!(" ") >>
-               event: alt!(event_start_shift | event_fall_asleep | event_wake_up) >>
-               (Entry{ year, month, day, hour, minute, event })
-       )
-);
+fn parse_entry(input: &str) -> IResult<&str, Entry> {
+    let (input, (_, year, _, month, _, day, _, hour, _, minute, _, _, event)) = tuple((
+        char('['),
+        number,
+        char('-'),
+        number,
+        char('-'),
+        number,
+        space1,
+        number,
+        char(':'),
+        number,
+        char(']'),
+        space1,
+        alt((event_start_shift, event_fall_asleep, event_wake_up)),
+    ))(input)?;
+
+    Ok((
+        input,
+        Entry {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            event,
+        },
+    ))
+}
 
-pub fn entry(line: &str) -> Option<Entry> {
-    parse_entry(CompleteStr(line)).ok().map(|x| x.1)
+pub fn entry(line: &str) -> aoc::Result<Entry> {
+    let trimmed = line.trim();
+    all_consuming(parse_entry)(trimmed)
+        .map(|(_, parsed)| parsed)
+        .map_err(|err| aoc::Error::from(err).locate(line, trimmed))
 }
 
 #[cfg(test)]
@@ -92,9 +88,9 @@ mod tests {
     #[test]
     fn test_parse_shift_start() {
         assert_eq!(
-            parse_entry(CompleteStr("[1518-03-19 00:02] Guard #647 begins shift")),
+            parse_entry("[1518-03-19 00:02] Guard #647 begins shift"),
             Ok((
-                CompleteStr(""),
+                "",
                 Entry {
                     year: 1518,
                     month: 3,
@@ -110,9 +106,9 @@ mod tests {
     #[test]
     fn test_parse_fall_asleep() {
         assert_eq!(
-            parse_entry(CompleteStr("[1518-11-04 00:24] falls asleep")),
+            parse_entry("[1518-11-04 00:24] falls asleep"),
             Ok((
-                CompleteStr(""),
+                "",
                 Entry {
                     year: 1518,
                     month: 11,
@@ -128,9 +124,9 @@ mod tests {
     #[test]
     fn test_parse_wake_up() {
         assert_eq!(
-            parse_entry(CompleteStr("[1518-05-07 00:26] wakes up")),
+            parse_entry("[1518-05-07 00:26] wakes up"),
             Ok((
-                CompleteStr(""),
+                "",
                 Entry {
                     year: 1518,
                     month: 5,
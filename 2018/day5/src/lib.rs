@@ -0,0 +1,105 @@
+use aoc::{ParseError, Solution};
+use std::collections::HashSet;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Polymer;
+
+    fn parse(&self, input: &str) -> aoc::Result<Polymer> {
+        Polymer::parse(input)
+    }
+
+    fn part1(&self, polymer: &Polymer) -> aoc::Result<aoc::Answer> {
+        Ok(polymer.react(None).into())
+    }
+
+    fn part2(&self, polymer: &Polymer) -> aoc::Result<aoc::Answer> {
+        Ok(polymer.shortest().into())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Polymer {
+    units: Vec<char>,
+    distinct_units: HashSet<char>,
+}
+
+impl Polymer {
+    /// Reads the units of a polymer, ignoring whitespace.
+    pub fn parse(input: &str) -> aoc::Result<Self> {
+        let mut units: Vec<char> = Vec::new();
+        let mut distinct_units: HashSet<char> = HashSet::new();
+
+        for (i, curr_unit) in input.char_indices() {
+            if curr_unit.is_alphabetic() {
+                units.push(curr_unit);
+                distinct_units.insert(curr_unit.to_ascii_lowercase());
+            } else if !curr_unit.is_whitespace() {
+                let msg = format!("expected a unit, found {:?}", curr_unit);
+                return Err(ParseError::at(input, i, msg).into());
+            }
+        }
+
+        if units.is_empty() {
+            return Err(ParseError::at(input, 0, "the polymer has no units").into());
+        }
+        Ok(Polymer {
+            units,
+            distinct_units,
+        })
+    }
+
+    pub fn react(&self, ignore_unit: Option<char>) -> usize {
+        let mut container: Vec<char> = Vec::new();
+        for unit in &self.units {
+            if ignore_unit
+                .filter(|u| u.eq_ignore_ascii_case(unit))
+                .is_some()
+            {
+                continue;
+            }
+
+            if let Some(curr_unit) = container.pop() {
+                if curr_unit.eq_ignore_ascii_case(unit) && curr_unit != *unit {
+                    continue;
+                }
+
+                container.push(curr_unit);
+            }
+            container.push(*unit);
+        }
+
+        container.len()
+    }
+
+    pub fn shortest(&self) -> usize {
+        self.distinct_units
+            .iter()
+            .map(|u| self.react(Some(*u)))
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polymer() -> aoc::Result<()> {
+        let polymer = Polymer::parse("dabAcCaCBAcCcaDA\n")?;
+
+        assert_eq!(10, polymer.react(None));
+        assert_eq!(4, polymer.shortest());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bad_polymer() {
+        assert!(Polymer::parse("").is_err());
+        assert!(Polymer::parse(" \n").is_err());
+        assert!(Polymer::parse("dabA1cC").is_err());
+        assert_eq!(0, Polymer::default().shortest());
+    }
+}
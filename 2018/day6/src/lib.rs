@@ -0,0 +1,26 @@
+use aoc::Solution;
+
+mod parse;
+
+pub use parse::Coord;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Coord>;
+
+    fn parse(&self, input: &str) -> aoc::Result<Vec<Coord>> {
+        let mut coords = aoc::parse::records(input, parse::coord)?;
+        coords.sort();
+
+        Ok(coords)
+    }
+
+    fn part1(&self, _coords: &Vec<Coord>) -> aoc::Result<aoc::Answer> {
+        Err(aoc::Error::Unsolved)
+    }
+
+    fn part2(&self, _coords: &Vec<Coord>) -> aoc::Result<aoc::Answer> {
+        Err(aoc::Error::Unsolved)
+    }
+}
@@ -1,28 +1,26 @@
-use nom::types::CompleteStr;
-use std::str::FromStr;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, space0},
+    combinator::{all_consuming, map_res},
+    sequence::{delimited, separated_pair},
+    IResult,
+};
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct Coord {
-    pub x: u32,
-    pub y: u32,
-}
+pub type Coord = aoc::grid::Point;
 
-fn to_uint<T>(input: CompleteStr) -> Result<T, T::Err>
-where
-    T: FromStr,
-{
-    input.parse::<T>()
+fn num_i64(input: &str) -> IResult<&str, i64> {
+    map_res(digit1, |s: &str| s.parse::<i64>())(input)
 }
 
-fn is_digit(c: char) -> bool {
-    c.is_digit(10)
+fn parse_coord(input: &str) -> IResult<&str, Coord> {
+    let (input, (x, y)) =
+        separated_pair(num_i64, delimited(space0, tag(","), space0), num_i64)(input)?;
+    Ok((input, Coord::new(x, y)))
 }
 
-named!(num_u32<CompleteStr, u32>, map_res!(take_while!(is_digit), to_uint::<u32>));
-
-named!(parse_coord<CompleteStr,Coord>,
-       ws!(do_parse!(x: num_u32 >> tag!(",") >> y: num_u32 >> (Coord{ x, y }))));
-
-pub fn coord(line: &str) -> Option<Coord> {
-    parse_coord(CompleteStr(line)).ok().map(|x| x.1)
+pub fn coord(line: &str) -> aoc::Result<Coord> {
+    let trimmed = line.trim();
+    all_consuming(parse_coord)(trimmed)
+        .map(|(_, parsed)| parsed)
+        .map_err(|err| aoc::Error::from(err).locate(line, trimmed))
 }
@@ -0,0 +1,334 @@
+use nom::error::ErrorKind;
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+use std::num;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum Error {
+    IOError(io::Error),
+    ParseIntError(num::ParseIntError),
+    ToStringError(std::string::FromUtf8Error),
+    Parse(Box<ParseError>),
+    BadOpCode(i64),
+    BadParamMode(i32),
+    ExecError(String),
+    BadLogEntry(String),
+    ReplayDiverged(usize),
+    BadPart(String),
+    Unsolved,
+    Usage(String),
+    BadAnswers(String),
+    InputNotFound {
+        year: u16,
+        day: u8,
+        tried: Vec<PathBuf>,
+    },
+    /// An error type particular to one puzzle.
+    Puzzle(Box<dyn StdError + Send + Sync>),
+}
+
+impl Error {
+    /// Pins this error to `fragment`, which must be a slice of `input`. A parse error positioned
+    /// within `fragment` moves to the same place in `input`; any other error becomes a parse
+    /// error at the start of `fragment`, caused by the original error.
+    pub fn locate(self, input: &str, fragment: &str) -> Error {
+        let base = offset_in(input, fragment);
+        let err = match self {
+            Error::Parse(err) => {
+                let offset = match err.anchor {
+                    Anchor::Start(offset) => base + offset,
+                    Anchor::End(len) => base + fragment.len().saturating_sub(len),
+                };
+                ParseError {
+                    file: err.file,
+                    source: err.source,
+                    ..ParseError::at(input, offset, err.message)
+                }
+            }
+            other => {
+                let message = match &other {
+                    Error::ParseIntError(err) => err.to_string(),
+                    other => other.to_string(),
+                };
+                ParseError::at(input, base, message).with_source(other)
+            }
+        };
+        Error::Parse(Box::new(err))
+    }
+
+    /// Records which file a parse error came from.
+    pub fn in_file(self, path: &Path) -> Error {
+        match self {
+            Error::Parse(mut err) => {
+                err.file = Some(path.to_path_buf());
+                Error::Parse(err)
+            }
+            other => other,
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::IOError(err) => Some(err),
+            Error::ParseIntError(err) => Some(err),
+            Error::ToStringError(err) => Some(err),
+            Error::Parse(err) => err.source(),
+            Error::Puzzle(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::IOError(err) => f.write_fmt(format_args!("IO error: {}", err)),
+            Error::ParseIntError(err) => f.write_fmt(format_args!("Parse error: {}", err)),
+            Error::ToStringError(err) => f.write_fmt(format_args!("ToString error: {}", err)),
+            Error::Parse(err) => err.fmt(f),
+            Error::BadOpCode(code) => f.write_fmt(format_args!("Bad op code: {}", code)),
+            Error::BadParamMode(mode) => f.write_fmt(format_args!("Bad parameter mode: {}", mode)),
+            Error::ExecError(msg) => f.write_fmt(format_args!("Exec error: {}", msg)),
+            Error::BadLogEntry(entry) => f.write_fmt(format_args!("Bad log entry: {}", entry)),
+            Error::ReplayDiverged(machine) => {
+                f.write_fmt(format_args!("Replay diverged on machine {}", machine))
+            }
+            Error::BadPart(part) => f.write_fmt(format_args!("Bad part: {}", part)),
+            Error::Unsolved => f.write_str("Not solved yet"),
+            Error::Usage(msg) => f.write_str(msg),
+            Error::BadAnswers(msg) => f.write_fmt(format_args!("Bad answers file: {}", msg)),
+            Error::InputNotFound { year, day, tried } => {
+                write!(f, "No input for {} day {}, tried:", year, day)?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            Error::Puzzle(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::IOError(err)
+    }
+}
+
+impl From<num::ParseIntError> for Error {
+    fn from(err: num::ParseIntError) -> Error {
+        Error::ParseIntError(err)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for Error {
+    fn from(err: std::string::FromUtf8Error) -> Error {
+        Error::ToStringError(err)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Error {
+        Error::Parse(Box::new(err))
+    }
+}
+
+/// A nom failure only knows the input that was left over, so the error is anchored to the end
+/// of whatever was being parsed. `locate` with the text that was handed to the parser to get the
+/// exact position.
+impl From<nom::Err<(&str, ErrorKind)>> for Error {
+    fn from(err: nom::Err<(&str, ErrorKind)>) -> Error {
+        let (rest, message) = match err {
+            nom::Err::Error((rest, kind)) | nom::Err::Failure((rest, kind)) => (
+                rest,
+                format!("unexpected input, expected {}", kind.description()),
+            ),
+            nom::Err::Incomplete(_) => ("", "unexpected end of input".to_string()),
+        };
+        Error::Parse(Box::new(ParseError {
+            anchor: Anchor::End(rest.len()),
+            ..ParseError::at(rest, 0, message)
+        }))
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Where a parse error sits in the text it was created from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Anchor {
+    /// Bytes from the start.
+    Start(usize),
+    /// Bytes before the end.
+    End(usize),
+}
+
+/// Input that failed to parse, with enough context to point at the problem:
+///
+/// ```text
+/// expected a direction
+///  --> 2019/day3/input:2:9
+///   |
+/// 2 | U62,R66,X55,R34
+///   |         ^
+/// ```
+#[derive(Debug)]
+pub struct ParseError {
+    message: String,
+    file: Option<PathBuf>,
+    anchor: Anchor,
+    line: usize,
+    column: usize,
+    text: String,
+    source: Option<Box<dyn StdError + Send + Sync>>,
+}
+
+impl ParseError {
+    /// An error at byte `offset` of `input`.
+    pub fn at<S: Into<String>>(input: &str, offset: usize, message: S) -> ParseError {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            message: message.into(),
+            file: None,
+            anchor: Anchor::Start(offset),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: input[line_start..].lines().next().unwrap_or("").to_string(),
+            source: None,
+        }
+    }
+
+    pub fn with_source<E: StdError + Send + Sync + 'static>(mut self, source: E) -> ParseError {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// 1-based.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// 1-based, in characters.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The whole line the error is on.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl StdError for ParseError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source
+            .as_ref()
+            .map(|err| err.as_ref() as &(dyn StdError + 'static))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let file = self
+            .file
+            .as_ref()
+            .map_or("input".into(), |path| path.display().to_string());
+        let line = self.line.to_string();
+        let gutter = " ".repeat(line.len());
+
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, file, self.line, self.column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", line, self.text)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+/// Byte offset of `fragment` within `input`, or 0 if it is not a slice of it.
+fn offset_in(input: &str, fragment: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let at = fragment.as_ptr() as usize;
+    if at >= start && at + fragment.len() <= start + input.len() {
+        at - start
+    } else {
+        0
+    }
+}
+
+#[test]
+fn test_parse_error_position() {
+    let input = "R8,U5\nU7,R6,X4\n";
+    let err = ParseError::at(input, 12, "expected a direction");
+    assert_eq!((err.line(), err.column()), (2, 7));
+    assert_eq!(err.text(), "U7,R6,X4");
+    assert_eq!(
+        Error::from(err)
+            .in_file(Path::new("day3/input"))
+            .to_string(),
+        "expected a direction\n --> day3/input:2:7\n  |\n2 | U7,R6,X4\n  |       ^"
+    );
+
+    // Offsets past the end, or inside a character, are pulled back.
+    let err = ParseError::at("aé", 2, "here");
+    assert_eq!((err.line(), err.column()), (1, 2));
+    assert_eq!(ParseError::at("", 5, "empty").column(), 1);
+}
+
+#[test]
+fn test_locate() {
+    let input = "12\n3x\n";
+    let line = input.lines().nth(1).unwrap();
+    let err = Error::from(line.parse::<i32>().unwrap_err()).locate(input, line);
+    match &err {
+        Error::Parse(parse) => {
+            assert_eq!((parse.line(), parse.column(), parse.text()), (2, 1, "3x"));
+            assert!(parse.message().contains("invalid digit"));
+        }
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+    let source = err.source().unwrap();
+    assert!(source.to_string().contains("invalid digit"));
+    assert!(source.source().is_some());
+
+    // Errors positioned within a fragment keep their place within it.
+    let err = Error::from(ParseError::at(line, 1, "bad unit")).locate(input, line);
+    match err {
+        Error::Parse(parse) => assert_eq!((parse.line(), parse.column()), (2, 2)),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_nom_errors() {
+    use nom::bytes::complete::tag;
+    use nom::combinator::all_consuming;
+
+    let input = "ok\nok!\n";
+    let line = input.lines().nth(1).unwrap();
+    let err = all_consuming(tag::<_, _, (&str, ErrorKind)>("ok"))(line).unwrap_err();
+    match Error::from(err).locate(input, line) {
+        Error::Parse(parse) => {
+            assert_eq!((parse.line(), parse.column(), parse.text()), (2, 3, "ok!"));
+            assert_eq!(parse.message(), "unexpected input, expected End of file");
+        }
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
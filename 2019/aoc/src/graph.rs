@@ -0,0 +1,486 @@
+//! Directed graphs over interned nodes, with the usual searches.
+//!
+//! Nodes are stored once and referred to by `NodeId`, so the searches work on plain indices
+//! and never clone the node values. Everything here is iterative: deep graphs (a chain of a
+//! million nodes, say) do not overflow the stack.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A node of a particular `Graph`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A directed graph with edges weighted by `W`.
+#[derive(Debug, Clone)]
+pub struct Graph<N, W = u32> {
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    successors: Vec<Vec<(NodeId, W)>>,
+    predecessors: Vec<Vec<NodeId>>,
+}
+
+impl<N, W> Default for Graph<N, W> {
+    fn default() -> Graph<N, W> {
+        Graph {
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            successors: Vec::new(),
+            predecessors: Vec::new(),
+        }
+    }
+}
+
+impl<N: Hash + Eq + Clone, W> Graph<N, W> {
+    pub fn new() -> Graph<N, W> {
+        Graph::default()
+    }
+
+    /// The id of `node`, adding it if it is new.
+    pub fn intern(&mut self, node: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(node.clone());
+        self.ids.insert(node, id);
+        self.successors.push(Vec::new());
+        self.predecessors.push(Vec::new());
+        id
+    }
+
+    /// The id of `node`, if it is in the graph.
+    pub fn id(&self, node: &N) -> Option<NodeId> {
+        self.ids.get(node).copied()
+    }
+
+    /// Adds an edge between two nodes, interning them first.
+    pub fn add_edge(&mut self, from: N, to: N, weight: W) -> (NodeId, NodeId) {
+        let (from, to) = (self.intern(from), self.intern(to));
+        self.connect(from, to, weight);
+        (from, to)
+    }
+}
+
+impl<N, W> Graph<N, W> {
+    /// Adds an edge between two nodes already in the graph.
+    pub fn connect(&mut self, from: NodeId, to: NodeId, weight: W) {
+        self.successors[from.0].push((to, weight));
+        self.predecessors[to.0].push(from);
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id.0]
+    }
+
+    /// Every node id, in the order the nodes were added.
+    pub fn ids(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.nodes.len()).map(NodeId)
+    }
+
+    /// The targets of the edges out of `id`, with their weights.
+    pub fn successors(&self, id: NodeId) -> impl Iterator<Item = (NodeId, &W)> {
+        self.successors[id.0].iter().map(|(to, w)| (*to, w))
+    }
+
+    /// The sources of the edges into `id`.
+    pub fn predecessors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.predecessors[id.0].iter().copied()
+    }
+
+    /// Nodes reachable from `start` in breadth first order, with the number of edges to each.
+    pub fn bfs(&self, start: NodeId) -> Bfs<'_, N, W> {
+        let mut seen = vec![false; self.len()];
+        seen[start.0] = true;
+        Bfs {
+            graph: self,
+            seen,
+            queue: vec![(start, 0)].into(),
+        }
+    }
+
+    /// Nodes reachable from `start` in depth first preorder.
+    pub fn dfs(&self, start: NodeId) -> Dfs<'_, N, W> {
+        Dfs {
+            graph: self,
+            seen: vec![false; self.len()],
+            stack: vec![start],
+        }
+    }
+
+    /// Orders the nodes so that every edge points forwards, or returns a node on a cycle.
+    pub fn topological_sort(&self) -> Result<Vec<NodeId>, NodeId> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Open,
+            Done,
+        }
+
+        let mut marks = vec![Mark::New; self.len()];
+        let mut order = Vec::with_capacity(self.len());
+
+        for root in self.ids() {
+            if marks[root.0] != Mark::New {
+                continue;
+            }
+
+            // Each entry is a node and how many of its successors have been visited.
+            let mut stack = vec![(root, 0)];
+            marks[root.0] = Mark::Open;
+            while let Some((id, next)) = stack.last_mut() {
+                let id = *id;
+                match self.successors[id.0].get(*next) {
+                    Some(&(child, _)) => {
+                        *next += 1;
+                        match marks[child.0] {
+                            Mark::New => {
+                                marks[child.0] = Mark::Open;
+                                stack.push((child, 0));
+                            }
+                            Mark::Open => return Err(child),
+                            Mark::Done => {}
+                        }
+                    }
+                    None => {
+                        marks[id.0] = Mark::Done;
+                        order.push(id);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        order.reverse();
+        Ok(order)
+    }
+}
+
+impl<N, W> Graph<N, W>
+where
+    W: Copy + Ord + Default + Add<Output = W>,
+{
+    /// The cheapest distance from `start` to every node, indexed by `NodeId::index`.
+    pub fn dijkstra(&self, start: NodeId) -> Vec<Option<W>> {
+        let mut dist = vec![None; self.len()];
+        let mut queue = BinaryHeap::new();
+        dist[start.0] = Some(W::default());
+        queue.push(Reverse((W::default(), start)));
+
+        while let Some(Reverse((cost, id))) = queue.pop() {
+            if dist[id.0].is_some_and(|best| cost > best) {
+                continue;
+            }
+            for &(next, weight) in &self.successors[id.0] {
+                let cost = cost + weight;
+                if dist[next.0].is_none_or(|best| cost < best) {
+                    dist[next.0] = Some(cost);
+                    queue.push(Reverse((cost, next)));
+                }
+            }
+        }
+
+        dist
+    }
+
+    /// The cheapest path from `start` to `goal` and its cost, guided by `estimate`, which must
+    /// never overestimate the remaining cost.
+    pub fn astar<F>(&self, start: NodeId, goal: NodeId, estimate: F) -> Option<(W, Vec<NodeId>)>
+    where
+        F: Fn(NodeId) -> W,
+    {
+        let mut best: Vec<Option<(W, Option<NodeId>)>> = vec![None; self.len()];
+        let mut queue = BinaryHeap::new();
+        best[start.0] = Some((W::default(), None));
+        queue.push(Candidate {
+            priority: estimate(start),
+            cost: W::default(),
+            id: start,
+        });
+
+        while let Some(Candidate { cost, id, .. }) = queue.pop() {
+            if id == goal {
+                let mut path = vec![goal];
+                while let Some((_, Some(prev))) = best[path[path.len() - 1].0] {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some((cost, path));
+            }
+            if best[id.0].is_some_and(|(known, _)| cost > known) {
+                continue;
+            }
+            for &(next, weight) in &self.successors[id.0] {
+                let cost = cost + weight;
+                if best[next.0].is_none_or(|(known, _)| cost < known) {
+                    best[next.0] = Some((cost, Some(id)));
+                    queue.push(Candidate {
+                        priority: cost + estimate(next),
+                        cost,
+                        id: next,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The cheapest path from `start` to `goal` and its cost.
+    pub fn shortest_path(&self, start: NodeId, goal: NodeId) -> Option<(W, Vec<NodeId>)> {
+        self.astar(start, goal, |_| W::default())
+    }
+}
+
+/// An entry in the A* queue, ordered so the lowest priority pops first.
+struct Candidate<W> {
+    priority: W,
+    cost: W,
+    id: NodeId,
+}
+
+impl<W: Ord> Ord for Candidate<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+impl<W: Ord> PartialOrd for Candidate<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Ord> PartialEq for Candidate<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: Ord> Eq for Candidate<W> {}
+
+pub struct Bfs<'a, N, W> {
+    graph: &'a Graph<N, W>,
+    seen: Vec<bool>,
+    queue: VecDeque<(NodeId, usize)>,
+}
+
+impl<N, W> Iterator for Bfs<'_, N, W> {
+    type Item = (NodeId, usize);
+
+    fn next(&mut self) -> Option<(NodeId, usize)> {
+        let (id, depth) = self.queue.pop_front()?;
+        for &(next, _) in &self.graph.successors[id.0] {
+            if !self.seen[next.0] {
+                self.seen[next.0] = true;
+                self.queue.push_back((next, depth + 1));
+            }
+        }
+        Some((id, depth))
+    }
+}
+
+pub struct Dfs<'a, N, W> {
+    graph: &'a Graph<N, W>,
+    seen: Vec<bool>,
+    stack: Vec<NodeId>,
+}
+
+impl<N, W> Iterator for Dfs<'_, N, W> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        while let Some(id) = self.stack.pop() {
+            if self.seen[id.0] {
+                continue;
+            }
+            self.seen[id.0] = true;
+            // Pushed in reverse so the first successor is visited first.
+            let successors = &self.graph.successors[id.0];
+            self.stack
+                .extend(successors.iter().rev().map(|&(next, _)| next));
+            return Some(id);
+        }
+        None
+    }
+}
+
+/// The breadth first spanning tree of a graph from a root, for ancestor queries.
+#[derive(Debug, Clone)]
+pub struct Tree {
+    root: NodeId,
+    parents: Vec<Option<NodeId>>,
+    depths: Vec<Option<usize>>,
+}
+
+impl Tree {
+    /// Every node reachable from `root`, each under the first node it was reached from.
+    pub fn new<N, W>(graph: &Graph<N, W>, root: NodeId) -> Tree {
+        let mut parents = vec![None; graph.len()];
+        let mut depths = vec![None; graph.len()];
+        depths[root.0] = Some(0);
+
+        let mut queue = VecDeque::from(vec![root]);
+        while let Some(id) = queue.pop_front() {
+            let depth = depths[id.0].map(|d| d + 1);
+            for &(child, _) in &graph.successors[id.0] {
+                if depths[child.0].is_none() {
+                    depths[child.0] = depth;
+                    parents[child.0] = Some(id);
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        Tree {
+            root,
+            parents,
+            depths,
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.depths[id.0].is_some()
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.parents[id.0]
+    }
+
+    /// The number of edges between the root and `id`, if `id` is in the tree.
+    pub fn depth(&self, id: NodeId) -> Option<usize> {
+        self.depths[id.0]
+    }
+
+    /// The parent of `id`, its parent, and so on up to the root.
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), move |&a| self.parent(a))
+    }
+
+    /// The deepest node that is `a` or an ancestor of it and `b` or an ancestor of it.
+    pub fn lowest_common_ancestor(&self, a: NodeId, b: NodeId) -> Option<NodeId> {
+        let (mut a, mut b) = (a, b);
+        let (mut depth_a, mut depth_b) = (self.depth(a)?, self.depth(b)?);
+        while depth_a > depth_b {
+            a = self.parents[a.0]?;
+            depth_a -= 1;
+        }
+        while depth_b > depth_a {
+            b = self.parents[b.0]?;
+            depth_b -= 1;
+        }
+        while a != b {
+            a = self.parents[a.0]?;
+            b = self.parents[b.0]?;
+        }
+        Some(a)
+    }
+
+    /// The number of tree edges between `a` and `b`.
+    pub fn distance(&self, a: NodeId, b: NodeId) -> Option<usize> {
+        let lca = self.lowest_common_ancestor(a, b)?;
+        Some(self.depth(a)? + self.depth(b)? - 2 * self.depth(lca)?)
+    }
+}
+
+#[cfg(test)]
+fn graph_of(edges: &[(&'static str, &'static str, u32)]) -> Graph<&'static str> {
+    let mut graph = Graph::new();
+    for &(from, to, weight) in edges {
+        graph.add_edge(from, to, weight);
+    }
+    graph
+}
+
+#[test]
+fn test_searches() {
+    let mut graph = graph_of(&[
+        ("a", "b", 7),
+        ("a", "c", 2),
+        ("c", "b", 3),
+        ("b", "d", 1),
+        ("c", "e", 10),
+        ("d", "e", 1),
+    ]);
+    let a = graph.intern("a");
+    assert_eq!((graph.intern("a"), graph.len()), (a, 5));
+    let graph = graph;
+    let id = |name| graph.id(&name).unwrap();
+    let names = |ids: Vec<NodeId>| ids.into_iter().map(|i| *graph.node(i)).collect::<Vec<_>>();
+
+    assert_eq!(graph.len(), 5);
+    assert_eq!(graph.predecessors(id("b")).count(), 2);
+
+    let bfs: Vec<_> = graph
+        .bfs(id("a"))
+        .map(|(i, d)| (*graph.node(i), d))
+        .collect();
+    assert_eq!(bfs, vec![("a", 0), ("b", 1), ("c", 1), ("d", 2), ("e", 2)]);
+    assert_eq!(
+        names(graph.dfs(id("a")).collect()),
+        vec!["a", "b", "d", "e", "c"]
+    );
+
+    let dist = graph.dijkstra(id("a"));
+    assert_eq!(dist[id("e").index()], Some(7));
+    assert_eq!(graph.dijkstra(id("e"))[id("a").index()], None);
+
+    let (cost, path) = graph.shortest_path(id("a"), id("e")).unwrap();
+    assert_eq!((cost, names(path)), (7, vec!["a", "c", "b", "d", "e"]));
+    assert_eq!(graph.shortest_path(id("e"), id("a")), None);
+
+    let order = names(graph.topological_sort().unwrap());
+    assert_eq!(order, vec!["a", "c", "b", "d", "e"]);
+    let cyclic = graph_of(&[("x", "y", 1), ("y", "z", 1), ("z", "x", 1)]);
+    assert!(cyclic.topological_sort().is_err());
+}
+
+#[test]
+fn test_tree() {
+    let graph = graph_of(&[
+        ("COM", "B", 1),
+        ("B", "C", 1),
+        ("C", "D", 1),
+        ("B", "E", 1),
+        ("E", "F", 1),
+        ("X", "Y", 1),
+    ]);
+    let id = |name| graph.id(&name).unwrap();
+    let tree = Tree::new(&graph, id("COM"));
+
+    assert_eq!(tree.depth(id("D")), Some(3));
+    assert_eq!(tree.depth(id("Y")), None);
+    assert!(!tree.contains(id("X")));
+    assert_eq!(
+        tree.ancestors(id("F")).collect::<Vec<_>>(),
+        vec![id("E"), id("B"), id("COM")]
+    );
+    assert_eq!(tree.lowest_common_ancestor(id("D"), id("F")), Some(id("B")));
+    assert_eq!(tree.lowest_common_ancestor(id("C"), id("D")), Some(id("C")));
+    assert_eq!(tree.lowest_common_ancestor(id("C"), id("Y")), None);
+    assert_eq!(tree.distance(id("D"), id("F")), Some(4));
+}
@@ -0,0 +1,681 @@
+//! Points, vectors and two-dimensional grids.
+//!
+//! Coordinates follow puzzle text: `x` grows to the right and `y` grows downwards. `Grid` is a
+//! dense rectangle anchored at the origin; `SparseGrid` holds only the cells that were set, at
+//! any coordinates.
+
+use crate::{parse, Result};
+use num::Integer;
+use std::collections::HashMap;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A position on a grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The displacement between two points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        (other - self).manhattan_len()
+    }
+
+    /// Up, right, down and left of this point.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Vector::ORTHOGONAL.iter().map(move |&v| self + v)
+    }
+
+    /// The 8 points around this one, clockwise from above.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Vector::ALL_AROUND.iter().map(move |&v| self + v)
+    }
+}
+
+impl Vector {
+    pub const UP: Vector = Vector { x: 0, y: -1 };
+    pub const DOWN: Vector = Vector { x: 0, y: 1 };
+    pub const LEFT: Vector = Vector { x: -1, y: 0 };
+    pub const RIGHT: Vector = Vector { x: 1, y: 0 };
+
+    const ORTHOGONAL: [Vector; 4] = [Vector::UP, Vector::RIGHT, Vector::DOWN, Vector::LEFT];
+    const ALL_AROUND: [Vector; 8] = [
+        Vector::new(0, -1),
+        Vector::new(1, -1),
+        Vector::new(1, 0),
+        Vector::new(1, 1),
+        Vector::new(0, 1),
+        Vector::new(-1, 1),
+        Vector::new(-1, 0),
+        Vector::new(-1, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Vector {
+        Vector { x, y }
+    }
+
+    pub fn manhattan_len(self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    /// A quarter turn clockwise, as seen on screen.
+    pub fn turn_right(self) -> Vector {
+        Vector::new(-self.y, self.x)
+    }
+
+    /// A quarter turn anticlockwise, as seen on screen.
+    pub fn turn_left(self) -> Vector {
+        Vector::new(self.y, -self.x)
+    }
+
+    /// The shortest vector pointing the same way with integer components. Zero stays zero.
+    pub fn reduced(self) -> Vector {
+        match self.x.gcd(&self.y) {
+            0 => self,
+            g => Vector::new(self.x / g, self.y / g),
+        }
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Point {
+        Point::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, n: i64) -> Vector {
+        Vector::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+/// An inclusive rectangle of points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// The `width` by `height` rectangle with its top left corner at `corner`. Empty if either
+    /// side is 0.
+    pub fn new(corner: Point, width: usize, height: usize) -> Bounds {
+        Bounds {
+            min: corner,
+            max: corner + Vector::new(width as i64 - 1, height as i64 - 1),
+        }
+    }
+
+    /// The smallest rectangle containing all `points`, if there are any.
+    pub fn around<I: IntoIterator<Item = Point>>(points: I) -> Option<Bounds> {
+        points.into_iter().fold(None, |bounds, p| {
+            Some(match bounds {
+                None => Bounds { min: p, max: p },
+                Some(Bounds { min, max }) => Bounds {
+                    min: Point::new(min.x.min(p.x), min.y.min(p.y)),
+                    max: Point::new(max.x.max(p.x), max.y.max(p.y)),
+                },
+            })
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1).max(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width() == 0 || self.height() == 0
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    pub fn intersection(&self, other: &Bounds) -> Bounds {
+        Bounds {
+            min: Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            max: Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        }
+    }
+
+    /// Every point, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Bounds { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+/// A rectangle of cells, `width` by `height`, with its top left corner at the origin.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` columns filled row by row from `cells`, whose length must be a
+    /// multiple of `width`.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells do not fill rows of {}",
+            cells.len(),
+            width
+        );
+        Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Reads a rectangle of characters, converting each with `cell`. Characters that `cell`
+    /// rejects are parse errors.
+    pub fn parse<F: Fn(char) -> Option<T>>(input: &str, cell: F) -> Result<Grid<T>> {
+        let rows = parse::grid(input, cell)?;
+        let width = rows.first().map_or(0, Vec::len);
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds::new(Point::ORIGIN, self.width, self.height)
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        if self.bounds().contains(p) {
+            Some(p.y as usize * self.width + p.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index(p).map(move |i| &mut self.cells[i])
+    }
+
+    /// Sets the cell at `p`, returning false if it is outside the grid.
+    pub fn set(&mut self, p: Point, value: T) -> bool {
+        match self.get_mut(p) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.bounds().points().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.bounds().points().zip(self.cells.iter_mut())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The positions of the cells for which `pred` holds.
+    pub fn positions<F: Fn(&T) -> bool>(&self, pred: F) -> Vec<Point> {
+        self.iter()
+            .filter(|(_, cell)| pred(cell))
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    /// The cells up, right, down and left of `p` that are inside the grid.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours4()
+            .filter_map(move |n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /// The cells around `p` that are inside the grid.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours8()
+            .filter_map(move |n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /// The part of the grid inside `bounds`, which is clipped to the grid.
+    pub fn view(&self, bounds: Bounds) -> View<'_, T> {
+        View {
+            grid: self,
+            bounds: bounds.intersection(&self.bounds()),
+        }
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// One line per row, each ending in a newline.
+    pub fn render<F: Fn(&T) -> char>(&self, cell: F) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            s.extend(row.iter().map(&cell));
+            s.push('\n');
+        }
+        s
+    }
+
+    /// The grid rearranged so that the cell at `p` comes from `source(p)` in this one, for a
+    /// result `width` by `height`.
+    fn rearrange<F>(&self, width: usize, height: usize, source: F) -> Grid<T>
+    where
+        T: Clone,
+        F: Fn(Point) -> Point,
+    {
+        let cells = Bounds::new(Point::ORIGIN, width, height)
+            .points()
+            .map(|p| self.get(source(p)).unwrap().clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// A quarter turn clockwise.
+    pub fn rotate_right(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let h = self.height as i64;
+        self.rearrange(self.height, self.width, |p| Point::new(p.y, h - 1 - p.x))
+    }
+
+    /// A quarter turn anticlockwise.
+    pub fn rotate_left(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let w = self.width as i64;
+        self.rearrange(self.height, self.width, |p| Point::new(w - 1 - p.y, p.x))
+    }
+
+    /// Mirrored left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let w = self.width as i64;
+        self.rearrange(self.width, self.height, |p| Point::new(w - 1 - p.x, p.y))
+    }
+
+    /// Mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let h = self.height as i64;
+        self.rearrange(self.width, self.height, |p| Point::new(p.x, h - 1 - p.y))
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |p| Point::new(p.y, p.x))
+    }
+}
+
+/// A rectangular window onto a `Grid`, with its own coordinates starting at the origin.
+#[derive(Debug, Clone, Copy)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    bounds: Bounds,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        self.bounds.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds.height()
+    }
+
+    /// Where the view sits in the underlying grid.
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    pub fn get(&self, p: Point) -> Option<&'a T> {
+        let local = Bounds::new(Point::ORIGIN, self.width(), self.height());
+        if local.contains(p) {
+            self.grid.get(self.bounds.min + (p - Point::ORIGIN))
+        } else {
+            None
+        }
+    }
+
+    /// Every cell in the view with its position in the view, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        let (grid, bounds) = (self.grid, self.bounds);
+        bounds
+            .points()
+            .map(move |p| (Point::ORIGIN + (p - bounds.min), grid.get(p).unwrap()))
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width(),
+            height: self.height(),
+            cells: self.iter().map(|(_, cell)| cell.clone()).collect(),
+        }
+    }
+}
+
+/// Cells at arbitrary points, only stored where set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    /// Reads a rectangle of characters, keeping the cells that `cell` converts and leaving the
+    /// rest empty.
+    pub fn parse<F: Fn(char) -> Option<T>>(input: &str, cell: F) -> SparseGrid<T> {
+        parse::cells(input)
+            .filter_map(|((x, y), c)| cell(c).map(|v| (Point::new(x as i64, y as i64), v)))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest rectangle containing every cell, if there are any.
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::around(self.cells.keys().copied())
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    /// The cell at `p`, inserting `default()` there first if it is empty.
+    pub fn entry<F: FnOnce() -> T>(&mut self, p: Point, default: F) -> &mut T {
+        self.cells.entry(p).or_insert_with(default)
+    }
+
+    /// Every cell with its position, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, cell)| (p, cell))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The cells up, right, down and left of `p` that are set.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours4()
+            .filter_map(move |n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /// The cells around `p` that are set.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours8()
+            .filter_map(move |n| self.get(n).map(|cell| (n, cell)))
+    }
+
+    /// One line per row of `bounds()`, each ending in a newline.
+    pub fn render<F: Fn(Option<&T>) -> char>(&self, cell: F) -> String {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        let mut s = String::new();
+        for y in bounds.min.y..=bounds.max.y {
+            s.extend((bounds.min.x..=bounds.max.x).map(|x| cell(self.get(Point::new(x, y)))));
+            s.push('\n');
+        }
+        s
+    }
+
+    /// A dense copy of `bounds()`, with empty cells set to `fill`, and the point that became
+    /// its origin.
+    pub fn to_dense(&self, fill: T) -> (Grid<T>, Point)
+    where
+        T: Clone,
+    {
+        let bounds = self
+            .bounds()
+            .unwrap_or_else(|| Bounds::new(Point::ORIGIN, 0, 0));
+        let cells = bounds
+            .points()
+            .map(|p| self.get(p).unwrap_or(&fill).clone())
+            .collect();
+        let grid = Grid {
+            width: bounds.width(),
+            height: bounds.height(),
+            cells,
+        };
+        (grid, bounds.min)
+    }
+}
+
+impl<T> std::iter::FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> SparseGrid<T> {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[test]
+fn test_points_and_vectors() {
+    let p = Point::new(2, 3);
+    assert_eq!(p + Vector::UP * 3, Point::new(2, 0));
+    assert_eq!(Point::new(-1, 5) - p, Vector::new(-3, 2));
+    assert_eq!(p.manhattan(Point::ORIGIN), 5);
+    assert_eq!(Vector::UP.turn_right(), Vector::RIGHT);
+    assert_eq!(Vector::UP.turn_left(), Vector::LEFT);
+    assert_eq!(-Vector::LEFT, Vector::RIGHT);
+    assert_eq!(Vector::new(-4, 6).reduced(), Vector::new(-2, 3));
+    assert_eq!(Vector::new(0, -7).reduced(), Vector::UP);
+    assert_eq!(p.neighbours4().count(), 4);
+    assert_eq!(
+        p.neighbours8().collect::<Vec<_>>()[..2],
+        [Point::new(2, 2), Point::new(3, 2)]
+    );
+
+    let bounds = Bounds::around(vec![Point::new(1, -1), Point::new(-2, 4)]).unwrap();
+    assert_eq!((bounds.width(), bounds.height()), (4, 6));
+    assert!(bounds.contains(Point::new(0, 0)) && !bounds.contains(Point::new(2, 0)));
+    assert_eq!(bounds.points().count(), 24);
+    assert!(Bounds::new(p, 0, 3).is_empty());
+}
+
+#[test]
+fn test_grid() {
+    let cell = |c: char| c.to_digit(10);
+    let mut grid = Grid::parse("123\n456\n", cell).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid.get(Point::new(2, 1)), Some(&6));
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.get(Point::new(0, -1)), None);
+    assert_eq!(
+        grid.neighbours4(Point::new(0, 0))
+            .map(|(_, &v)| v)
+            .collect::<Vec<_>>(),
+        vec![2, 4]
+    );
+    assert_eq!(grid.neighbours8(Point::new(1, 0)).count(), 5);
+    assert!(Grid::parse("12\n4", cell).is_err());
+
+    let digit = |&v: &u32| std::char::from_digit(v, 10).unwrap();
+    assert_eq!(grid.rotate_right().render(digit), "41\n52\n63\n");
+    assert_eq!(grid.rotate_left().render(digit), "36\n25\n14\n");
+    assert_eq!(grid.flip_horizontal().render(digit), "321\n654\n");
+    assert_eq!(grid.flip_vertical().render(digit), "456\n123\n");
+    assert_eq!(grid.transpose().render(digit), "14\n25\n36\n");
+    assert_eq!(grid.rotate_right().rotate_left(), grid);
+
+    let view = grid.view(Bounds::new(Point::new(1, 0), 5, 5));
+    assert_eq!((view.width(), view.height()), (2, 2));
+    assert_eq!(view.get(Point::new(1, 1)), Some(&6));
+    assert_eq!(view.to_grid().render(digit), "23\n56\n");
+
+    assert!(grid.set(Point::new(0, 1), 9));
+    assert!(!grid.set(Point::new(0, 2), 9));
+    assert_eq!(
+        grid.positions(|&v| v > 5),
+        vec![Point::new(0, 1), Point::new(2, 1)]
+    );
+}
+
+#[test]
+fn test_sparse_grid() {
+    let mut grid = SparseGrid::parse(".#.\n..#\n", |c| if c == '#' { Some(()) } else { None });
+    assert_eq!(grid.len(), 2);
+    assert!(grid.contains(Point::new(1, 0)));
+    assert_eq!(grid.neighbours8(Point::new(1, 0)).count(), 1);
+
+    grid.insert(Point::new(-1, -1), ());
+    assert_eq!(
+        grid.bounds(),
+        Some(Bounds {
+            min: Point::new(-1, -1),
+            max: Point::new(2, 1),
+        })
+    );
+    let draw = |cell: Option<&()>| if cell.is_some() { '#' } else { '.' };
+    assert_eq!(grid.render(draw), "#...\n..#.\n...#\n");
+
+    let marked: SparseGrid<bool> = grid.points().map(|p| (p, true)).collect();
+    let (dense, origin) = marked.to_dense(false);
+    assert_eq!(origin, Point::new(-1, -1));
+    assert_eq!(dense.positions(|&set| set).len(), 3);
+    assert_eq!(SparseGrid::<()>::new().render(draw), "");
+}
@@ -0,0 +1,216 @@
+//! Finding the puzzle input for a given year and day.
+//!
+//! A `Resolver` tries, in order:
+//!
+//! 1. an explicit path, or failing that the file named by `$AOC_INPUT`,
+//! 2. the day's own `input` file, `<repo>/<year>/day<N>/input`,
+//! 3. the per-year inputs directory, `<repo>/<year>/inputs/day<N>`,
+//! 4. the cache, `<cache>/<account>/<year>/day<N>`.
+//!
+//! An explicit path is only ever used on its own: if one is given, it is the only path tried.
+//! That makes it, and so `$AOC_INPUT`, only meaningful for a single day; the runner refuses to
+//! run more than one day with either.
+//!
+//! Puzzle inputs differ between accounts. Selecting an account (or setting `$AOC_ACCOUNT`)
+//! skips the day's own `input`, which belongs to the default account, and looks in
+//! `<repo>/<year>/inputs/<account>/day<N>` and `<cache>/<account>/<year>/day<N>` instead. The
+//! cache lives in `$AOC_CACHE`, `$XDG_CACHE_HOME/aoc` or `~/.cache/aoc`, whichever is set first.
+
+use crate::{Error, Result};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_ACCOUNT: &str = "default";
+
+/// The root of this repository.
+pub fn repo_root() -> PathBuf {
+    let aoc_crate = Path::new(env!("CARGO_MANIFEST_DIR"));
+    aoc_crate
+        .ancestors()
+        .nth(2)
+        .unwrap_or(aoc_crate)
+        .to_path_buf()
+}
+
+/// Reads the input for `year` and `day` using a resolver configured from the environment.
+pub fn read(year: u16, day: u8) -> Result<String> {
+    Resolver::from_env().read(year, day)
+}
+
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    root: PathBuf,
+    path: Option<PathBuf>,
+    account: Option<String>,
+    cache: Option<PathBuf>,
+}
+
+impl Resolver {
+    /// Looks only in the repository at `root`, without consulting the environment.
+    pub fn new<P: Into<PathBuf>>(root: P) -> Resolver {
+        Resolver {
+            root: root.into(),
+            path: None,
+            account: None,
+            cache: None,
+        }
+    }
+
+    /// Looks in this repository, honouring `$AOC_INPUT`, `$AOC_ACCOUNT` and the cache variables.
+    pub fn from_env() -> Resolver {
+        let var = |name: &str| env::var_os(name).filter(|v| !v.is_empty());
+        let cache = var("AOC_CACHE")
+            .map(PathBuf::from)
+            .or_else(|| var("XDG_CACHE_HOME").map(|dir| Path::new(&dir).join("aoc")))
+            .or_else(|| var("HOME").map(|dir| Path::new(&dir).join(".cache/aoc")));
+
+        Resolver {
+            root: repo_root(),
+            path: var("AOC_INPUT").map(PathBuf::from),
+            account: var("AOC_ACCOUNT").map(|a| a.to_string_lossy().into_owned()),
+            cache,
+        }
+    }
+
+    /// Uses `path` and nothing else.
+    pub fn path<P: Into<PathBuf>>(mut self, path: P) -> Resolver {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// The path used for every day, if there is one.
+    pub fn explicit_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Uses the inputs of another account.
+    pub fn account(mut self, account: &str) -> Resolver {
+        self.account = Some(account.to_string());
+        self
+    }
+
+    /// The account whose inputs are used, if not the default one.
+    pub fn account_name(&self) -> Option<&str> {
+        self.account.as_deref()
+    }
+
+    pub fn cache_dir<P: Into<PathBuf>>(mut self, dir: P) -> Resolver {
+        self.cache = Some(dir.into());
+        self
+    }
+
+    /// The paths that would be tried for `year` and `day`, in order.
+    pub fn candidates(&self, year: u16, day: u8) -> Vec<PathBuf> {
+        if let Some(path) = &self.path {
+            return vec![path.clone()];
+        }
+
+        let year_dir = self.root.join(year.to_string());
+        let file = format!("day{}", day);
+        let mut candidates = Vec::new();
+
+        match &self.account {
+            None => {
+                candidates.push(year_dir.join(&file).join("input"));
+                candidates.push(year_dir.join("inputs").join(&file));
+            }
+            Some(account) => candidates.push(year_dir.join("inputs").join(account).join(&file)),
+        }
+        if let Some(path) = self.cache_path(year, day) {
+            candidates.push(path);
+        }
+
+        candidates
+    }
+
+    /// The first candidate that exists.
+    pub fn resolve(&self, year: u16, day: u8) -> Result<PathBuf> {
+        let candidates = self.candidates(year, day);
+        match candidates.iter().find(|path| path.is_file()) {
+            Some(path) => Ok(path.clone()),
+            None => Err(Error::InputNotFound {
+                year,
+                day,
+                tried: candidates,
+            }),
+        }
+    }
+
+    pub fn read(&self, year: u16, day: u8) -> Result<String> {
+        Ok(fs::read_to_string(self.resolve(year, day)?)?)
+    }
+
+    /// Saves `input` in the cache, so that it is found from then on. Returns where it was saved.
+    pub fn store(&self, year: u16, day: u8, input: &str) -> Result<PathBuf> {
+        let path = self
+            .cache_path(year, day)
+            .ok_or_else(|| Error::Usage("no cache directory: set AOC_CACHE or HOME".to_string()))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, input)?;
+        Ok(path)
+    }
+
+    fn cache_path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        let account = self.account.as_deref().unwrap_or(DEFAULT_ACCOUNT);
+        self.cache.as_ref().map(|dir| {
+            dir.join(account)
+                .join(year.to_string())
+                .join(format!("day{}", day))
+        })
+    }
+}
+
+#[test]
+fn test_candidates() {
+    let resolver = Resolver::new("/repo").cache_dir("/cache");
+    assert_eq!(
+        resolver.candidates(2019, 7),
+        vec![
+            PathBuf::from("/repo/2019/day7/input"),
+            PathBuf::from("/repo/2019/inputs/day7"),
+            PathBuf::from("/cache/default/2019/day7"),
+        ]
+    );
+
+    assert_eq!(
+        resolver.clone().account("bob").candidates(2019, 7),
+        vec![
+            PathBuf::from("/repo/2019/inputs/bob/day7"),
+            PathBuf::from("/cache/bob/2019/day7"),
+        ]
+    );
+
+    assert_eq!(resolver.explicit_path(), None);
+    let resolver = resolver.path("mine.txt");
+    assert_eq!(
+        resolver.candidates(2019, 7),
+        vec![PathBuf::from("mine.txt")]
+    );
+    assert_eq!(resolver.explicit_path(), Some(Path::new("mine.txt")));
+}
+
+#[test]
+fn test_resolve() {
+    let dir = env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+    let resolver = Resolver::new(dir.join("repo")).cache_dir(dir.join("cache"));
+
+    match resolver.resolve(2019, 7) {
+        Err(Error::InputNotFound { year, day, tried }) => {
+            assert_eq!((year, day), (2019, 7));
+            assert_eq!(tried, resolver.candidates(2019, 7));
+        }
+        other => panic!("expected InputNotFound, got {:?}", other),
+    }
+    let message = resolver.resolve(2019, 7).unwrap_err().to_string();
+    assert!(message.contains("2019 day 7") && message.contains("day7/input"));
+
+    let stored = resolver.store(2019, 7, "3,0,4,0,99").unwrap();
+    assert_eq!(resolver.resolve(2019, 7).unwrap(), stored);
+    assert_eq!(resolver.read(2019, 7).unwrap(), "3,0,4,0,99");
+    assert!(resolver.clone().account("bob").read(2019, 7).is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,525 @@
+use crate::Result;
+use crossbeam::crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender};
+use instructions::{Args, Instruction, InstructionSet, Param};
+use recording::{Direction, Recorder};
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub mod compiler;
+pub mod heatmap;
+pub mod instructions;
+pub mod recording;
+
+/// The most memory a computer may use, in cells. Addresses at or past it are as bad as negative
+/// ones.
+pub const MEMORY_LIMIT: usize = 1 << 24;
+
+#[derive(Debug)]
+enum Input {
+    Channel(Receiver<i64>),
+    Replay(VecDeque<i64>),
+}
+
+/// The I/O channels of a computer, as seen by instruction semantics.
+#[derive(Debug)]
+pub struct IO {
+    input: Input,
+    output_tx: Sender<i64>,
+    deadline: Option<Instant>,
+    recorder: Option<(usize, Recorder)>,
+}
+
+impl IO {
+    /// Takes the next input value, or the state the computer should stop in if none is
+    /// available.
+    pub fn read(&mut self) -> std::result::Result<i64, State> {
+        let val = match (&mut self.input, self.deadline) {
+            (Input::Replay(values), _) => values.pop_front().ok_or(State::InputExhausted)?,
+            (Input::Channel(rx), Some(deadline)) => {
+                match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(val) => val,
+                    Err(RecvTimeoutError::Timeout) => return Err(State::BudgetExhausted),
+                    Err(RecvTimeoutError::Disconnected) => return Err(State::InputExhausted),
+                }
+            }
+            (Input::Channel(rx), None) => rx.recv().map_err(|_| State::InputExhausted)?,
+        };
+
+        if let Some((machine, recorder)) = &self.recorder {
+            recorder.record(*machine, Direction::Input, val);
+        }
+        Ok(val)
+    }
+
+    pub fn write(&mut self, val: i64) {
+        // Record before sending so that the consumer's input event is always timestamped later.
+        if let Some((machine, recorder)) = &self.recorder {
+            recorder.record(*machine, Direction::Output, val);
+        }
+        self.output_tx.send(val).unwrap()
+    }
+}
+
+/// Limits on how much work a single call to `Computer::run_with_budget` may do. A timeout
+/// counts from the start of each call, so one budget can be reused across resumed runs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Budget {
+    instructions: Option<u64>,
+    timeout: Option<Duration>,
+}
+
+impl Budget {
+    pub fn unlimited() -> Budget {
+        Budget::default()
+    }
+
+    pub fn instructions(limit: u64) -> Budget {
+        Budget::default().with_instructions(limit)
+    }
+
+    pub fn timeout(limit: Duration) -> Budget {
+        Budget::default().with_timeout(limit)
+    }
+
+    pub fn with_instructions(self, limit: u64) -> Budget {
+        Budget {
+            instructions: Some(limit),
+            ..self
+        }
+    }
+
+    pub fn with_timeout(self, limit: Duration) -> Budget {
+        Budget {
+            timeout: Some(limit),
+            ..self
+        }
+    }
+
+    fn is_exhausted(&self, executed: u64, deadline: Option<Instant>) -> bool {
+        self.instructions.is_some_and(|limit| executed >= limit)
+            || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Outcome of a budgeted run. A computer that ran out of budget can be resumed by calling
+/// `run_with_budget` (or `run`) again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Halted(i64),
+    BudgetExhausted,
+    /// A replaying computer asked for more input than was recorded, or every sender of input
+    /// has gone.
+    InputExhausted,
+    /// The instruction at the current position is not in the computer's instruction set, has
+    /// bad parameter modes, runs past the end of memory, names an address that is negative or
+    /// past `MEMORY_LIMIT`, or jumps to a negative position. Past the end, the opcode is 0.
+    BadOpCode(i64),
+}
+
+/// Observes the data reads and writes made by a running computer. `cycle` is the number of
+/// instructions executed before the access.
+pub trait Probe: Send + fmt::Debug {
+    fn on_read(&mut self, cycle: u64, addr: usize);
+    fn on_write(&mut self, cycle: u64, addr: usize);
+}
+
+impl<P: Probe> Probe for Arc<Mutex<P>> {
+    fn on_read(&mut self, cycle: u64, addr: usize) {
+        self.lock().unwrap().on_read(cycle, addr)
+    }
+
+    fn on_write(&mut self, cycle: u64, addr: usize) {
+        self.lock().unwrap().on_write(cycle, addr)
+    }
+}
+
+#[derive(Debug)]
+pub struct Computer {
+    memory: Vec<i64>,
+    ptr: usize,
+    relative_base: i64,
+    cycles: u64,
+    io: IO,
+    probe: Option<Box<dyn Probe>>,
+    instructions: InstructionSet,
+}
+
+pub fn load_input(input_file: &str) -> Result<Vec<i64>> {
+    parse_program(&std::fs::read_to_string(input_file)?)
+}
+
+/// Parses comma separated Intcode, ignoring surrounding whitespace.
+pub fn parse_program(input: &str) -> Result<Vec<i64>> {
+    crate::parse::csv(input)
+}
+
+pub fn new(initial_state: &[i64], input_rx: Receiver<i64>) -> (Computer, Receiver<i64>) {
+    with_input(initial_state, Input::Channel(input_rx))
+}
+
+fn with_input(initial_state: &[i64], input: Input) -> (Computer, Receiver<i64>) {
+    let (output_tx, output_rx) = unbounded();
+
+    (
+        Computer {
+            memory: initial_state.to_vec(),
+            ptr: 0,
+            relative_base: 0,
+            cycles: 0,
+            io: IO {
+                input,
+                output_tx,
+                deadline: None,
+                recorder: None,
+            },
+            probe: None,
+            instructions: InstructionSet::day9(),
+        },
+        output_rx,
+    )
+}
+
+impl Computer {
+    pub fn run(&mut self) -> i64 {
+        match self.run_with_budget(Budget::unlimited()) {
+            State::Halted(result) => result,
+            state => panic!("computer stopped without halting: {:?}", state),
+        }
+    }
+
+    pub fn run_with_budget(&mut self, budget: Budget) -> State {
+        let deadline = budget.timeout.map(|limit| Instant::now() + limit);
+        self.io.deadline = deadline;
+
+        let mut executed = 0;
+        loop {
+            if budget.is_exhausted(executed, deadline) {
+                return State::BudgetExhausted;
+            }
+
+            let actions = match self.decode() {
+                Some((instruction, args)) => instruction.execute(&args, &mut self.io),
+                None => return self.bad_op_code(),
+            };
+
+            for action in actions {
+                match action {
+                    Action::Write(dest, val) => self.store(dest, val),
+                    Action::MoveRel(slots) => self.ptr += slots,
+                    Action::MoveAbs(dest) => match usize::try_from(dest) {
+                        Ok(dest) => self.ptr = dest,
+                        Err(_) => return self.bad_op_code(),
+                    },
+                    Action::SetRelativeOffset(offset) => {
+                        match self.relative_base.checked_add(offset) {
+                            Some(base) => self.relative_base = base,
+                            None => return self.bad_op_code(),
+                        }
+                    }
+                    Action::Suspend(state) => return state,
+                    Action::Halt => {
+                        let result = self.memory.first().copied().unwrap_or(0);
+                        if let Some((machine, recorder)) = &self.io.recorder {
+                            recorder.record(*machine, Direction::Halt, result);
+                        }
+                        return State::Halted(result);
+                    }
+                }
+            }
+
+            executed += 1;
+            self.cycles += 1;
+        }
+    }
+
+    /// Replaces the instruction set, which defaults to `InstructionSet::day9()`.
+    pub fn set_instructions(&mut self, instructions: InstructionSet) {
+        self.instructions = instructions;
+    }
+
+    /// Total number of instructions executed since the computer was created.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Logs every value read or written by this computer to `recorder`, tagged with `machine`.
+    pub fn record(&mut self, machine: usize, recorder: Recorder) {
+        self.io.recorder = Some((machine, recorder));
+    }
+
+    /// Reports every subsequent memory access to `probe`, replacing any previous probe.
+    pub fn attach_probe<P: Probe + 'static>(&mut self, probe: P) {
+        self.probe = Some(Box::new(probe));
+    }
+
+    /// The instruction pointer and relative base.
+    pub fn registers(&self) -> (usize, i64) {
+        (self.ptr, self.relative_base)
+    }
+
+    /// Moves the instruction pointer and relative base, e.g. to resume in the interpreter after
+    /// running compiled code.
+    pub fn set_registers(&mut self, ptr: usize, relative_base: i64) {
+        self.ptr = ptr;
+        self.relative_base = relative_base;
+    }
+
+    /// Reads a memory cell, treating cells past the end of memory as zero.
+    pub fn load(&mut self, addr: usize) -> i64 {
+        if let Some(probe) = self.probe.as_mut() {
+            probe.on_read(self.cycles, addr);
+        }
+        self.memory.get(addr).copied().unwrap_or(0)
+    }
+
+    /// Writes a memory cell, growing memory as needed.
+    pub fn store(&mut self, addr: usize, val: i64) {
+        if let Some(probe) = self.probe.as_mut() {
+            probe.on_write(self.cycles, addr);
+        }
+        if addr >= self.memory.len() {
+            self.memory.resize(addr + 1, 0);
+        }
+        self.memory[addr] = val
+    }
+
+    /// Takes the next input value without any deadline.
+    pub fn input(&mut self) -> std::result::Result<i64, State> {
+        self.io.deadline = None;
+        self.io.read()
+    }
+
+    pub fn output(&mut self, val: i64) {
+        self.io.write(val)
+    }
+
+    fn bad_op_code(&self) -> State {
+        State::BadOpCode(self.memory.get(self.ptr).copied().unwrap_or(0))
+    }
+
+    /// The instruction at the pointer and its arguments, or `None` if there is no valid one:
+    /// an unknown or negative opcode, a bad mode, operands past the end of memory or a bad
+    /// address.
+    fn decode(&mut self) -> Option<(Arc<Instruction>, Args)> {
+        let code = *self.memory.get(self.ptr)?;
+        if code < 0 {
+            return None;
+        }
+        let instruction = self.instructions.get(code % 100)?;
+        let modes = ParamModes::from(code / 100)?;
+
+        let mut values = Vec::new();
+        let mut dest = None;
+        for (i, param) in instruction.params().iter().enumerate() {
+            let mode = modes.get(i + 1);
+            let p = self.operand(i + 1)?;
+            match param {
+                Param::Read => values.push(self.get_param(p, mode)?),
+                Param::Write => dest = Some(self.address(p, mode)?),
+            }
+        }
+
+        Some((instruction, Args::new(values, dest)))
+    }
+
+    fn operand(&self, num: usize) -> Option<i64> {
+        self.memory.get(self.ptr.checked_add(num)?).copied()
+    }
+
+    fn get_param(&mut self, p: i64, mode: Mode) -> Option<i64> {
+        match mode {
+            Mode::Immediate => Some(p),
+            _ => {
+                let addr = self.address(p, mode)?;
+                Some(self.load(addr))
+            }
+        }
+    }
+
+    /// The address a parameter refers to, if it is one the computer can reach.
+    fn address(&self, p: i64, mode: Mode) -> Option<usize> {
+        let addr = match mode {
+            Mode::Position => p,
+            Mode::Relative => self.relative_base.checked_add(p)?,
+            Mode::Immediate => return None,
+        };
+        usize::try_from(addr)
+            .ok()
+            .filter(|&addr| addr < MEMORY_LIMIT)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+struct ParamModes {
+    modes: Vec<Mode>,
+}
+
+impl ParamModes {
+    /// The modes in the digits of `code`, lowest first, or `None` if a digit is not a mode.
+    fn from(code: i64) -> Option<ParamModes> {
+        let mut modes: Vec<Mode> = Vec::new();
+
+        let mut rest = code;
+        while rest > 0 {
+            match rest % 10 {
+                0 => modes.push(Mode::Position),
+                1 => modes.push(Mode::Immediate),
+                2 => modes.push(Mode::Relative),
+                _ => return None,
+            }
+            rest /= 10;
+        }
+
+        Some(ParamModes { modes })
+    }
+
+    fn get(&self, param_num: usize) -> Mode {
+        self.modes
+            .get(param_num - 1)
+            .copied()
+            .unwrap_or(Mode::Position)
+    }
+}
+
+/// State changes requested by an instruction, applied in order by the computer.
+#[derive(Debug)]
+pub enum Action {
+    Write(usize, i64),
+    /// Jumps to a position given by the program, which is bad if negative.
+    MoveAbs(i64),
+    MoveRel(usize),
+    SetRelativeOffset(i64),
+    Suspend(State),
+    Halt,
+}
+
+#[test]
+fn test_instruction_budget() {
+    // Counts memory[9] down from 5 before halting.
+    let initial_state = vec![1001, 9, -1, 9, 1005, 9, 0, 99, 0, 5];
+    let (_tx, rx) = unbounded();
+    let (mut comp, _output) = new(&initial_state, rx);
+
+    assert_eq!(
+        comp.run_with_budget(Budget::instructions(4)),
+        State::BudgetExhausted
+    );
+    assert_eq!(comp.cycles(), 4);
+
+    assert_eq!(
+        comp.run_with_budget(Budget::instructions(100)),
+        State::Halted(1001)
+    );
+    assert_eq!(comp.cycles(), 10);
+}
+
+#[test]
+fn test_runaway_program() {
+    let initial_state = vec![1105, 1, 0];
+    let (_tx, rx) = unbounded();
+    let (mut comp, _output) = new(&initial_state, rx);
+
+    assert_eq!(
+        comp.run_with_budget(Budget::instructions(1000)),
+        State::BudgetExhausted
+    );
+    assert_eq!(
+        comp.run_with_budget(Budget::timeout(Duration::from_millis(10))),
+        State::BudgetExhausted
+    );
+}
+
+#[test]
+fn test_resume_after_input_timeout() {
+    let initial_state = vec![3, 0, 4, 0, 99];
+    let (tx, rx) = unbounded();
+    let (mut comp, output) = new(&initial_state, rx);
+
+    assert_eq!(
+        comp.run_with_budget(Budget::timeout(Duration::from_millis(10))),
+        State::BudgetExhausted
+    );
+    assert_eq!(comp.cycles(), 0);
+
+    tx.send(42).unwrap();
+    assert_eq!(comp.run(), 42);
+    assert_eq!(output.try_recv(), Ok(42));
+}
+
+#[test]
+fn test_malformed_instructions() {
+    let run = |initial_state: &[i64]| {
+        let (_tx, rx) = unbounded();
+        let (mut comp, _output) = new(initial_state, rx);
+        comp.run_with_budget(Budget::instructions(100))
+    };
+
+    // A mode digit of 3.
+    assert_eq!(run(&[301, 0, 0, 0, 99]), State::BadOpCode(301));
+    // Immediate mode for the destination.
+    assert_eq!(run(&[10001, 0, 0, 0, 99]), State::BadOpCode(10001));
+    assert_eq!(run(&[-1, 0, 0, 0, 99]), State::BadOpCode(-1));
+    // Running off the end, and an instruction whose operands are cut off.
+    assert_eq!(run(&[1101, 1, 1, 0]), State::BadOpCode(0));
+    assert_eq!(run(&[1101, 1, 1, 0, 1101, 2]), State::BadOpCode(1101));
+
+    // Writes, reads and jumps to addresses that are negative or too big.
+    assert_eq!(run(&[1101, 1, 1, -5, 99]), State::BadOpCode(1101));
+    assert_eq!(
+        run(&[1101, 1, 1, 1_000_000_000_000, 99]),
+        State::BadOpCode(1101)
+    );
+    assert_eq!(run(&[1001, -3, 1, 5, 99]), State::BadOpCode(1001));
+    assert_eq!(run(&[109, -10, 1201, 0, 1, 0, 99]), State::BadOpCode(1201));
+    assert_eq!(
+        run(&[109, 1, 2201, i64::MAX, 0, 0, 99]),
+        State::BadOpCode(2201)
+    );
+    assert_eq!(run(&[1105, 1, -1]), State::BadOpCode(1105));
+    assert_eq!(run(&[109, i64::MAX, 109, 1, 99]), State::BadOpCode(109));
+}
+
+#[test]
+fn test_reused_timeout() {
+    let initial_state = vec![3, 0, 4, 0, 3, 0, 4, 0, 99];
+    let (tx, rx) = unbounded();
+    let (mut comp, output) = new(&initial_state, rx);
+
+    let budget = Budget::timeout(Duration::from_millis(50));
+    std::thread::sleep(Duration::from_millis(60));
+    tx.send(1).unwrap();
+    assert_eq!(comp.run_with_budget(budget), State::BudgetExhausted);
+    assert_eq!(output.try_recv(), Ok(1));
+
+    // The same budget gives the resumed run its own 50ms.
+    tx.send(2).unwrap();
+    drop(tx);
+    assert_eq!(comp.run_with_budget(budget), State::Halted(2));
+    assert_eq!(output.try_recv(), Ok(2));
+}
+
+#[test]
+fn test_disconnected_input() {
+    let (tx, rx) = unbounded();
+    let (mut comp, _output) = new(&[3, 0, 99], rx);
+    drop(tx);
+    assert_eq!(
+        comp.run_with_budget(Budget::unlimited()),
+        State::InputExhausted
+    );
+
+    let (tx, rx) = unbounded();
+    let (mut comp, _output) = new(&[3, 0, 99], rx);
+    drop(tx);
+    let budget = Budget::timeout(Duration::from_secs(10));
+    assert_eq!(comp.run_with_budget(budget), State::InputExhausted);
+}
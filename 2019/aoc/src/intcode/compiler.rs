@@ -0,0 +1,362 @@
+//! Ahead-of-time translation of Intcode programs into Rust.
+//!
+//! `compile` produces the source of a function `fn(&mut Computer) -> State` that runs the
+//! program natively, starting from the computer's current registers. Every instruction reachable
+//! from address 0 gets an arm in a `match` on the program counter that executes straight-line
+//! code up to the end of its basic block.
+//!
+//! The generated code bakes in the program's instructions, so it is only valid for the exact
+//! program it was compiled from. Whenever that assumption could break it hands over to the
+//! interpreter, which resumes from the same registers:
+//!
+//! * programs that write into their own code through a fixed address are not compiled at all,
+//! * relative-mode writes are checked at run time against the compiled code range,
+//! * jumps to addresses that were not compiled fall back on arrival.
+//!
+//! Compiled code does not count cycles.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+const IMMEDIATE: i64 = 1;
+const RELATIVE: i64 = 2;
+
+#[derive(Debug)]
+struct Op {
+    code: i64,
+    modes: [i64; 3],
+    params: Vec<i64>,
+}
+
+impl Op {
+    fn len(&self) -> usize {
+        self.params.len() + 1
+    }
+
+    fn read(&self, i: usize) -> String {
+        let p = self.params[i];
+        match self.modes[i] {
+            IMMEDIATE => format!("({}i64)", p),
+            RELATIVE => format!("c.load((rb + ({})) as usize)", p),
+            _ => format!("c.load({})", p),
+        }
+    }
+
+    /// Position-mode write destinations are known statically.
+    fn static_dest(&self) -> Option<usize> {
+        let i = self.write_param()?;
+        if self.modes[i] == RELATIVE {
+            None
+        } else {
+            Some(self.params[i] as usize)
+        }
+    }
+
+    fn write_param(&self) -> Option<usize> {
+        match self.code {
+            1 | 2 | 7 | 8 => Some(2),
+            3 => Some(0),
+            _ => None,
+        }
+    }
+
+    fn is_jump(&self) -> bool {
+        self.code == 5 || self.code == 6
+    }
+
+    fn static_target(&self) -> Option<usize> {
+        if self.is_jump() && self.modes[1] == IMMEDIATE && self.params[1] >= 0 {
+            Some(self.params[1] as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// Decodes the day9 instruction at `addr`, rejecting anything the interpreter would not run.
+fn decode(program: &[i64], addr: usize) -> Option<Op> {
+    let value = *program.get(addr)?;
+    if value < 0 {
+        return None;
+    }
+
+    let code = value % 100;
+    let arity = match code {
+        1 | 2 | 7 | 8 => 3,
+        5 | 6 => 2,
+        3 | 4 | 9 => 1,
+        99 => 0,
+        _ => return None,
+    };
+
+    let mut modes = [0; 3];
+    let mut rest = value / 100;
+    for mode in modes.iter_mut().take(arity) {
+        *mode = rest % 10;
+        rest /= 10;
+    }
+    if rest != 0 || modes.iter().any(|&m| m > RELATIVE) {
+        return None;
+    }
+
+    let params = program.get(addr + 1..addr + 1 + arity)?.to_vec();
+    let op = Op {
+        code,
+        modes,
+        params,
+    };
+
+    if let Some(i) = op.write_param() {
+        if op.modes[i] == IMMEDIATE || (op.modes[i] != RELATIVE && op.params[i] < 0) {
+            return None;
+        }
+    }
+    if op.modes[..arity]
+        .iter()
+        .zip(&op.params)
+        .any(|(&m, &p)| m == 0 && p < 0)
+    {
+        return None;
+    }
+
+    Some(op)
+}
+
+/// The instructions reachable from address 0 by static control flow, and the addresses that
+/// start a basic block.
+struct Analysis {
+    ops: BTreeMap<usize, Op>,
+    leaders: BTreeSet<usize>,
+    code: Vec<bool>,
+}
+
+fn analyse(program: &[i64]) -> Analysis {
+    let mut ops = BTreeMap::new();
+    let mut leaders = BTreeSet::new();
+    let mut pending = vec![0];
+    leaders.insert(0);
+
+    while let Some(addr) = pending.pop() {
+        if ops.contains_key(&addr) {
+            continue;
+        }
+        let op = match decode(program, addr) {
+            Some(op) => op,
+            None => continue,
+        };
+
+        if op.code != 99 {
+            pending.push(addr + op.len());
+        }
+        if op.is_jump() {
+            leaders.insert(addr + op.len());
+            if let Some(target) = op.static_target() {
+                leaders.insert(target);
+                pending.push(target);
+            }
+        }
+        ops.insert(addr, op);
+    }
+
+    let mut code = vec![false; program.len()];
+    for (addr, op) in &ops {
+        for cell in code.iter_mut().skip(*addr).take(op.len()) {
+            *cell = true;
+        }
+    }
+
+    Analysis { ops, leaders, code }
+}
+
+/// Whether some reachable instruction writes to a fixed address inside the reachable code.
+pub fn self_modifies(program: &[i64]) -> bool {
+    let analysis = analyse(program);
+    analysis
+        .ops
+        .values()
+        .filter_map(Op::static_dest)
+        .any(|dest| analysis.code.get(dest).copied().unwrap_or(false))
+}
+
+/// Translates `program` into the source of `pub fn <name>(&mut Computer) -> State`.
+///
+/// Self-modifying programs compile to a function that simply runs the interpreter.
+pub fn compile(program: &[i64], name: &str) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "/// Compiled from a {} word Intcode program.",
+        program.len()
+    )
+    .unwrap();
+    writeln!(out, "#[allow(unused, clippy::all)]").unwrap();
+    writeln!(
+        out,
+        "pub fn {}(c: &mut aoc::intcode::Computer) -> aoc::intcode::State {{",
+        name
+    )
+    .unwrap();
+
+    if self_modifies(program) {
+        writeln!(out, "    // Self-modifying: left to the interpreter.").unwrap();
+        writeln!(
+            out,
+            "    c.run_with_budget(aoc::intcode::Budget::unlimited())"
+        )
+        .unwrap();
+        writeln!(out, "}}").unwrap();
+        return out;
+    }
+
+    let analysis = analyse(program);
+    write!(out, "    static CODE: [bool; {}] = [", analysis.code.len()).unwrap();
+    for (i, is_code) in analysis.code.iter().enumerate() {
+        if i % 16 == 0 {
+            write!(out, "\n        ").unwrap();
+        }
+        write!(out, "{}, ", is_code).unwrap();
+    }
+    writeln!(out, "\n    ];").unwrap();
+    writeln!(out, "    let (mut pc, mut rb) = c.registers();").unwrap();
+    writeln!(out, "    loop {{").unwrap();
+    writeln!(out, "        match pc {{").unwrap();
+    for addr in analysis.ops.keys() {
+        writeln!(out, "            {} => {{", addr).unwrap();
+        emit_block(&mut out, &analysis, *addr);
+        writeln!(out, "            }}").unwrap();
+    }
+    writeln!(out, "            _ => {{").unwrap();
+    emit_fallback(&mut out, "pc");
+    writeln!(out, "            }}").unwrap();
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+const INDENT: &str = "                ";
+
+fn emit_fallback(out: &mut String, pc: &str) {
+    writeln!(out, "{}c.set_registers({}, rb);", INDENT, pc).unwrap();
+    writeln!(
+        out,
+        "{}return c.run_with_budget(aoc::intcode::Budget::unlimited());",
+        INDENT
+    )
+    .unwrap();
+}
+
+/// Stores the local `val` at the instruction's destination.
+fn emit_store(out: &mut String, op: &Op, next: usize) {
+    let i = op.write_param().unwrap();
+    match op.static_dest() {
+        Some(dest) => writeln!(out, "{}c.store({}, val);", INDENT, dest).unwrap(),
+        None => {
+            writeln!(
+                out,
+                "{}let dest = (rb + ({})) as usize;",
+                INDENT, op.params[i]
+            )
+            .unwrap();
+            writeln!(out, "{}c.store(dest, val);", INDENT).unwrap();
+            writeln!(out, "{}if dest < CODE.len() && CODE[dest] {{", INDENT).unwrap();
+            emit_fallback(out, &next.to_string());
+            writeln!(out, "{}}}", INDENT).unwrap();
+        }
+    }
+}
+
+/// Emits the instructions from `start` to the end of its basic block. Every path through the
+/// emitted code ends in `continue` or `return`.
+fn emit_block(out: &mut String, analysis: &Analysis, start: usize) {
+    let mut addr = start;
+    loop {
+        let op = &analysis.ops[&addr];
+        let next = addr + op.len();
+
+        match op.code {
+            1 | 2 | 7 | 8 => {
+                let (lhs, rhs) = (op.read(0), op.read(1));
+                let val = match op.code {
+                    1 => format!("{} + {}", lhs, rhs),
+                    2 => format!("{} * {}", lhs, rhs),
+                    7 => format!("({} < {}) as i64", lhs, rhs),
+                    _ => format!("({} == {}) as i64", lhs, rhs),
+                };
+                writeln!(out, "{}let val = {};", INDENT, val).unwrap();
+                emit_store(out, op, next);
+            }
+            3 => {
+                writeln!(out, "{}let val = match c.input() {{", INDENT).unwrap();
+                writeln!(out, "{}    Ok(val) => val,", INDENT).unwrap();
+                writeln!(out, "{}    Err(state) => {{", INDENT).unwrap();
+                writeln!(out, "{}        c.set_registers({}, rb);", INDENT, addr).unwrap();
+                writeln!(out, "{}        return state;", INDENT).unwrap();
+                writeln!(out, "{}    }}", INDENT).unwrap();
+                writeln!(out, "{}}};", INDENT).unwrap();
+                emit_store(out, op, next);
+            }
+            4 => {
+                writeln!(out, "{}let val = {};", INDENT, op.read(0)).unwrap();
+                writeln!(out, "{}c.output(val);", INDENT).unwrap();
+            }
+            5 | 6 => {
+                let cmp = if op.code == 5 { "!=" } else { "==" };
+                let target = match op.static_target() {
+                    Some(target) => target.to_string(),
+                    None => format!("{} as usize", op.read(1)),
+                };
+                writeln!(out, "{}if {} {} 0 {{", INDENT, op.read(0), cmp).unwrap();
+                writeln!(out, "{}    pc = {};", INDENT, target).unwrap();
+                writeln!(out, "{}    continue;", INDENT).unwrap();
+                writeln!(out, "{}}}", INDENT).unwrap();
+            }
+            9 => writeln!(out, "{}rb += {};", INDENT, op.read(0)).unwrap(),
+            99 => {
+                writeln!(out, "{}c.set_registers({}, rb);", INDENT, addr).unwrap();
+                writeln!(
+                    out,
+                    "{}return aoc::intcode::State::Halted(c.load(0));",
+                    INDENT
+                )
+                .unwrap();
+                return;
+            }
+            _ => unreachable!(),
+        }
+
+        if analysis.ops.contains_key(&next) && !analysis.leaders.contains(&next) {
+            addr = next;
+        } else {
+            writeln!(out, "{}pc = {};", INDENT, next).unwrap();
+            writeln!(out, "{}continue;", INDENT).unwrap();
+            return;
+        }
+    }
+}
+
+#[test]
+fn test_self_modifies() {
+    // Day 2 style: the first instruction writes into the operands of the second.
+    assert!(self_modifies(&[1, 0, 0, 3, 99]));
+    assert!(self_modifies(&[1101, 104, 0, 4, 99, 42, 99]));
+    // Writes past the code only.
+    assert!(!self_modifies(&[1101, 1, 2, 5, 99, 0]));
+    // Writes through the relative base are checked at run time instead.
+    assert!(!self_modifies(&[109, 9, 21101, 104, 0, 0, 1105, 1, 9, 99]));
+}
+
+#[test]
+fn test_compile_blocks() {
+    let source = compile(&[1001, 9, -1, 9, 1005, 9, 0, 99, 0, 5], "countdown");
+
+    assert!(source.contains("pub fn countdown(c: &mut aoc::intcode::Computer)"));
+    assert!(source.contains("            0 => {"));
+    assert!(source.contains("            7 => {"));
+    assert!(source.contains("            4 => {"));
+    assert!(source.contains("let val = c.load(9) + (-1i64);\n                c.store(9, val);"));
+    assert!(!source.contains("left to the interpreter"));
+
+    let source = compile(&[1, 0, 0, 3, 99], "patched");
+    assert!(source.contains("left to the interpreter"));
+}
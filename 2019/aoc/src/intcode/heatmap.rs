@@ -0,0 +1,304 @@
+use super::Probe;
+use std::collections::BTreeMap;
+use std::fmt::Write as FmtWrite;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Read and write counts for each memory cell touched during a window of execution. Counts are
+/// kept per touched cell, so a few far-flung addresses cost no more than nearby ones.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frame {
+    reads: BTreeMap<usize, u32>,
+    writes: BTreeMap<usize, u32>,
+}
+
+impl Frame {
+    pub fn reads(&self, addr: usize) -> u32 {
+        self.reads.get(&addr).copied().unwrap_or(0)
+    }
+
+    pub fn writes(&self, addr: usize) -> u32 {
+        self.writes.get(&addr).copied().unwrap_or(0)
+    }
+
+    /// Number of cells up to and including the highest address accessed.
+    pub fn len(&self) -> usize {
+        let last = |counts: &BTreeMap<usize, u32>| {
+            counts
+                .keys()
+                .next_back()
+                .map(|&addr| addr.saturating_add(1))
+        };
+        std::cmp::max(last(&self.reads), last(&self.writes)).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn record(counts: &mut BTreeMap<usize, u32>, addr: usize) {
+        *counts.entry(addr).or_insert(0) += 1;
+    }
+
+    fn merge(&mut self, other: &Frame) {
+        for (&addr, n) in &other.reads {
+            *self.reads.entry(addr).or_insert(0) += n;
+        }
+        for (&addr, n) in &other.writes {
+            *self.writes.entry(addr).or_insert(0) += n;
+        }
+    }
+
+    /// Colour of a cell: writes drive the red channel and reads the blue channel, both scaled
+    /// logarithmically against the busiest cell in the frame.
+    fn colour(&self, addr: usize, max: u32) -> (u8, u8, u8) {
+        let scale = |n: u32| {
+            if max == 0 {
+                0
+            } else {
+                (255.0 * (1.0 + n as f64).ln() / (1.0 + max as f64).ln()) as u8
+            }
+        };
+
+        (scale(self.writes(addr)), 0, scale(self.reads(addr)))
+    }
+
+    fn max_count(&self) -> u32 {
+        self.reads
+            .values()
+            .chain(self.writes.values())
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Rows needed to lay the frame out `width` cells to a row.
+    fn height(&self, width: usize) -> io::Result<usize> {
+        if width == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "heatmaps need at least one cell per row",
+            ));
+        }
+        Ok(self.len().div_ceil(width))
+    }
+
+    /// The colour of every cell, row by row, padded out to a whole number of rows.
+    fn rgb(&self, width: usize, height: usize) -> Vec<u8> {
+        let max = self.max_count();
+        (0..width * height)
+            .flat_map(|addr| {
+                let (r, g, b) = self.colour(addr, max);
+                [r, g, b]
+            })
+            .collect()
+    }
+
+    /// Writes the frame as a binary PPM image with `width` cells per row.
+    pub fn write_ppm<W: Write>(&self, out: &mut W, width: usize) -> io::Result<()> {
+        let height = self.height(width)?;
+        write!(out, "P6\n{} {}\n255\n", width, height)?;
+        out.write_all(&self.rgb(width, height))
+    }
+
+    /// Writes the frame as an RGB PNG image with `width` cells per row.
+    pub fn write_png<W: Write>(&self, out: &mut W, width: usize) -> io::Result<()> {
+        let height = self.height(width)?;
+        let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+        encoder.set_color(png::ColorType::RGB);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()?
+            .write_image_data(&self.rgb(width, height))?;
+        Ok(())
+    }
+
+    /// Renders the frame with 24-bit ANSI background colours, two columns per cell.
+    pub fn to_ansi(&self, width: usize) -> io::Result<String> {
+        self.height(width)?;
+        let max = self.max_count();
+        let mut s = String::new();
+
+        for addr in 0..self.len() {
+            let (r, g, b) = self.colour(addr, max);
+            write!(s, "\x1b[48;2;{};{};{}m  ", r, g, b).unwrap();
+            if (addr + 1) % width == 0 {
+                s.push_str("\x1b[0m\n");
+            }
+        }
+
+        if !self.len().is_multiple_of(width) {
+            s.push_str("\x1b[0m\n");
+        }
+        Ok(s)
+    }
+}
+
+/// A `Probe` that counts memory accesses per cell, optionally split into frames covering a
+/// fixed number of cycles each.
+#[derive(Debug, Default)]
+pub struct Heatmap {
+    window: Option<u64>,
+    frames: Vec<Frame>,
+    current: Frame,
+}
+
+impl Heatmap {
+    pub fn new() -> Heatmap {
+        Heatmap::default()
+    }
+
+    pub fn with_window(cycles: u64) -> Heatmap {
+        Heatmap {
+            window: Some(cycles),
+            ..Heatmap::default()
+        }
+    }
+
+    /// Per-window frames in execution order. Without a window there is a single frame.
+    pub fn frames(&self) -> impl Iterator<Item = &Frame> {
+        self.frames.iter().chain(std::iter::once(&self.current))
+    }
+
+    /// Counts accumulated over the whole run.
+    pub fn total(&self) -> Frame {
+        self.frames().fold(Frame::default(), |mut acc, f| {
+            acc.merge(f);
+            acc
+        })
+    }
+
+    /// Plays the frames back in a terminal, redrawing in place every `delay`.
+    pub fn animate_ansi<W: Write>(
+        &self,
+        out: &mut W,
+        width: usize,
+        delay: Duration,
+    ) -> io::Result<()> {
+        for frame in self.frames() {
+            write!(out, "\x1b[H\x1b[2J{}", frame.to_ansi(width)?)?;
+            out.flush()?;
+            thread::sleep(delay);
+        }
+
+        Ok(())
+    }
+
+    fn advance(&mut self, cycle: u64) {
+        if let Some(window) = self.window {
+            while cycle >= (self.frames.len() as u64 + 1) * window {
+                let frame = std::mem::take(&mut self.current);
+                self.frames.push(frame);
+            }
+        }
+    }
+}
+
+impl Probe for Heatmap {
+    fn on_read(&mut self, cycle: u64, addr: usize) {
+        self.advance(cycle);
+        Frame::record(&mut self.current.reads, addr);
+    }
+
+    fn on_write(&mut self, cycle: u64, addr: usize) {
+        self.advance(cycle);
+        Frame::record(&mut self.current.writes, addr);
+    }
+}
+
+#[cfg(test)]
+fn run_with_heatmap(initial_state: &[i64], heatmap: Heatmap) -> Heatmap {
+    use crossbeam::crossbeam_channel::unbounded;
+    use std::sync::{Arc, Mutex};
+
+    let (_tx, rx) = unbounded();
+    let (mut comp, _output) = super::new(initial_state, rx);
+    let heatmap = Arc::new(Mutex::new(heatmap));
+    comp.attach_probe(heatmap.clone());
+    comp.run();
+    drop(comp);
+
+    Arc::try_unwrap(heatmap).unwrap().into_inner().unwrap()
+}
+
+#[test]
+fn test_heatmap_counts() {
+    // memory[4] = memory[0] * memory[4]; halt
+    let initial_state = vec![1002, 4, 3, 4, 33];
+    let total = run_with_heatmap(&initial_state, Heatmap::new()).total();
+
+    assert_eq!(total.reads(4), 1);
+    assert_eq!(total.writes(4), 1);
+    assert_eq!(total.reads(3), 0);
+    assert_eq!(total.len(), 5);
+}
+
+#[test]
+fn test_heatmap_frames() {
+    // Counts memory[9] down from 5, reading it twice and writing it once per iteration.
+    let initial_state = vec![1001, 9, -1, 9, 1005, 9, 0, 99, 0, 5];
+    let heatmap = run_with_heatmap(&initial_state, Heatmap::with_window(4));
+    let frames: Vec<&Frame> = heatmap.frames().collect();
+
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0].reads(9), 4);
+    assert_eq!(frames[0].writes(9), 2);
+    assert_eq!(frames[2].reads(9), 2);
+    assert_eq!(heatmap.total().reads(9), 10);
+}
+
+#[test]
+fn test_sparse_addresses() {
+    let mut heatmap = Heatmap::new();
+    heatmap.on_write(0, 1 << 40);
+    heatmap.on_read(1, 3);
+    heatmap.on_read(2, 1 << 40);
+    let total = heatmap.total();
+
+    assert_eq!(total.writes(1 << 40), 1);
+    assert_eq!(total.reads(1 << 40), 1);
+    assert_eq!(total.reads(3), 1);
+    assert_eq!(total.len(), (1 << 40) + 1);
+}
+
+#[test]
+fn test_write_ppm() {
+    let initial_state = vec![1002, 4, 3, 4, 33];
+    let total = run_with_heatmap(&initial_state, Heatmap::new()).total();
+
+    let mut buf: Vec<u8> = Vec::new();
+    total.write_ppm(&mut buf, 4).unwrap();
+
+    let header = b"P6\n4 2\n255\n";
+    assert_eq!(&buf[..header.len()], header);
+    assert_eq!(buf.len(), header.len() + 4 * 2 * 3);
+    assert_eq!(
+        &buf[header.len() + 4 * 3..header.len() + 5 * 3],
+        &[255, 0, 255]
+    );
+}
+
+#[test]
+fn test_write_png() {
+    let initial_state = vec![1002, 4, 3, 4, 33];
+    let total = run_with_heatmap(&initial_state, Heatmap::new()).total();
+
+    let mut buf: Vec<u8> = Vec::new();
+    total.write_png(&mut buf, 4).unwrap();
+    let (info, mut reader) = png::Decoder::new(&buf[..]).read_info().unwrap();
+    assert_eq!((info.width, info.height), (4, 2));
+    let mut pixels = vec![0; info.buffer_size()];
+    reader.next_frame(&mut pixels).unwrap();
+    assert_eq!(&pixels[4 * 3..5 * 3], &[255, 0, 255]);
+}
+
+#[test]
+fn test_zero_width() {
+    let total = run_with_heatmap(&[99], Heatmap::new()).total();
+    let mut buf: Vec<u8> = Vec::new();
+    assert!(total.write_ppm(&mut buf, 0).is_err());
+    assert!(total.write_png(&mut buf, 0).is_err());
+    assert!(total.to_ansi(0).is_err());
+    assert!(buf.is_empty());
+}
@@ -0,0 +1,289 @@
+use super::{Action, IO};
+use std::fmt;
+use std::ops::Index;
+use std::sync::Arc;
+
+/// How an instruction uses a parameter. `Read` parameters are resolved to values according to
+/// their mode, `Write` parameters to the address being written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Param {
+    Read,
+    Write,
+}
+
+/// The resolved parameters of an instruction: the values of its `Read` parameters, indexed in
+/// order, and the address named by its `Write` parameter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    values: Vec<i64>,
+    dest: Option<usize>,
+}
+
+impl Args {
+    pub fn new(values: Vec<i64>, dest: Option<usize>) -> Args {
+        Args { values, dest }
+    }
+
+    /// The address to write to. Panics for an instruction without a `Write` parameter.
+    pub fn dest(&self) -> usize {
+        self.dest.expect("instruction has no Write parameter")
+    }
+}
+
+impl Index<usize> for Args {
+    type Output = i64;
+
+    fn index(&self, i: usize) -> &i64 {
+        &self.values[i]
+    }
+}
+
+type Semantics = dyn Fn(&Args, &mut IO) -> Vec<Action> + Send + Sync;
+
+pub struct Instruction {
+    name: String,
+    params: Vec<Param>,
+    semantics: Box<Semantics>,
+}
+
+impl Instruction {
+    /// `semantics` receives the resolved `params`, of which at most one may be `Write`, and
+    /// must return the actions to apply, including moving the instruction pointer.
+    pub fn new<F>(name: &str, params: &[Param], semantics: F) -> Instruction
+    where
+        F: Fn(&Args, &mut IO) -> Vec<Action> + Send + Sync + 'static,
+    {
+        assert!(
+            params.iter().filter(|&&p| p == Param::Write).count() <= 1,
+            "{} has more than one Write parameter",
+            name
+        );
+        Instruction {
+            name: name.to_string(),
+            params: params.to_vec(),
+            semantics: Box::new(semantics),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn params(&self) -> &[Param] {
+        &self.params
+    }
+
+    pub(super) fn execute(&self, args: &Args, io: &mut IO) -> Vec<Action> {
+        (self.semantics)(args, io)
+    }
+}
+
+impl fmt::Debug for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Instruction")
+            .field("name", &self.name)
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+/// Maps two-digit op codes to instructions.
+#[derive(Debug, Clone)]
+pub struct InstructionSet {
+    table: Vec<Option<Arc<Instruction>>>,
+}
+
+impl InstructionSet {
+    pub fn empty() -> InstructionSet {
+        InstructionSet {
+            table: vec![None; 100],
+        }
+    }
+
+    /// Add, multiply and halt.
+    pub fn day2() -> InstructionSet {
+        let mut set = InstructionSet::empty();
+        set.register(
+            1,
+            Instruction::new("add", &[Param::Read, Param::Read, Param::Write], add),
+        );
+        set.register(
+            2,
+            Instruction::new("mul", &[Param::Read, Param::Read, Param::Write], mul),
+        );
+        set.register(99, Instruction::new("halt", &[], halt));
+        set
+    }
+
+    /// The day2 set plus I/O, jumps and comparisons.
+    pub fn day5() -> InstructionSet {
+        let mut set = InstructionSet::day2();
+        set.register(3, Instruction::new("inp", &[Param::Write], inp));
+        set.register(4, Instruction::new("out", &[Param::Read], out));
+        set.register(
+            5,
+            Instruction::new("jt", &[Param::Read, Param::Read], jump_true),
+        );
+        set.register(
+            6,
+            Instruction::new("jf", &[Param::Read, Param::Read], jump_false),
+        );
+        set.register(
+            7,
+            Instruction::new("lt", &[Param::Read, Param::Read, Param::Write], less_than),
+        );
+        set.register(
+            8,
+            Instruction::new("eq", &[Param::Read, Param::Read, Param::Write], equals),
+        );
+        set
+    }
+
+    /// The day5 set plus relative base adjustment.
+    pub fn day9() -> InstructionSet {
+        let mut set = InstructionSet::day5();
+        set.register(
+            9,
+            Instruction::new("rbo", &[Param::Read], relative_base_offset),
+        );
+        set
+    }
+
+    /// Adds or replaces the instruction for `op_code`, which must be in `0..100`.
+    pub fn register(&mut self, op_code: i64, instruction: Instruction) {
+        assert!(
+            (0..100).contains(&op_code),
+            "op code out of range: {}",
+            op_code
+        );
+        self.table[op_code as usize] = Some(Arc::new(instruction));
+    }
+
+    pub fn get(&self, op_code: i64) -> Option<Arc<Instruction>> {
+        if (0..100).contains(&op_code) {
+            self.table[op_code as usize].clone()
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for InstructionSet {
+    fn default() -> InstructionSet {
+        InstructionSet::day9()
+    }
+}
+
+fn add(args: &Args, _io: &mut IO) -> Vec<Action> {
+    vec![
+        Action::Write(args.dest(), args[0] + args[1]),
+        Action::MoveRel(4),
+    ]
+}
+
+fn mul(args: &Args, _io: &mut IO) -> Vec<Action> {
+    vec![
+        Action::Write(args.dest(), args[0] * args[1]),
+        Action::MoveRel(4),
+    ]
+}
+
+fn inp(args: &Args, io: &mut IO) -> Vec<Action> {
+    match io.read() {
+        Ok(val) => vec![Action::Write(args.dest(), val), Action::MoveRel(2)],
+        Err(state) => vec![Action::Suspend(state)],
+    }
+}
+
+fn out(args: &Args, io: &mut IO) -> Vec<Action> {
+    io.write(args[0]);
+    vec![Action::MoveRel(2)]
+}
+
+fn jump_true(args: &Args, _io: &mut IO) -> Vec<Action> {
+    if args[0] != 0 {
+        vec![Action::MoveAbs(args[1])]
+    } else {
+        vec![Action::MoveRel(3)]
+    }
+}
+
+fn jump_false(args: &Args, _io: &mut IO) -> Vec<Action> {
+    if args[0] == 0 {
+        vec![Action::MoveAbs(args[1])]
+    } else {
+        vec![Action::MoveRel(3)]
+    }
+}
+
+fn less_than(args: &Args, _io: &mut IO) -> Vec<Action> {
+    let val = if args[0] < args[1] { 1 } else { 0 };
+    vec![Action::Write(args.dest(), val), Action::MoveRel(4)]
+}
+
+fn equals(args: &Args, _io: &mut IO) -> Vec<Action> {
+    let val = if args[0] == args[1] { 1 } else { 0 };
+    vec![Action::Write(args.dest(), val), Action::MoveRel(4)]
+}
+
+fn relative_base_offset(args: &Args, _io: &mut IO) -> Vec<Action> {
+    vec![Action::SetRelativeOffset(args[0]), Action::MoveRel(2)]
+}
+
+fn halt(_args: &Args, _io: &mut IO) -> Vec<Action> {
+    vec![Action::Halt]
+}
+
+#[test]
+fn test_presets() {
+    use super::State;
+    use crossbeam::crossbeam_channel::unbounded;
+
+    // Outputs 3 * 4 using relative mode.
+    let initial_state = vec![109, 10, 22202, 0, 1, 2, 204, 2, 99, 0, 3, 4, 0];
+
+    let (_tx, rx) = unbounded();
+    let (mut comp, output) = super::new(&initial_state, rx.clone());
+    assert_eq!(
+        comp.run_with_budget(super::Budget::unlimited()),
+        State::Halted(109)
+    );
+    assert_eq!(output.try_recv(), Ok(12));
+
+    let (mut comp, _output) = super::new(&initial_state, rx.clone());
+    comp.set_instructions(InstructionSet::day5());
+    assert_eq!(
+        comp.run_with_budget(super::Budget::unlimited()),
+        State::BadOpCode(109)
+    );
+
+    let (mut comp, _output) = super::new(&[1, 0, 0, 0, 4, 0, 99], rx);
+    comp.set_instructions(InstructionSet::day2());
+    assert_eq!(
+        comp.run_with_budget(super::Budget::unlimited()),
+        State::BadOpCode(4)
+    );
+}
+
+#[test]
+fn test_custom_instruction() {
+    use crossbeam::crossbeam_channel::unbounded;
+
+    let mut set = InstructionSet::day9();
+    set.register(
+        42,
+        Instruction::new("dbg", &[Param::Read, Param::Read], |args, io| {
+            io.write(args[0]);
+            io.write(args[1]);
+            vec![Action::MoveRel(3)]
+        }),
+    );
+    assert_eq!(set.get(42).unwrap().name(), "dbg");
+
+    let (_tx, rx) = unbounded();
+    let (mut comp, output) = super::new(&[1042, 4, 7, 99, -1], rx);
+    comp.set_instructions(set);
+    comp.run();
+
+    assert_eq!(output.try_iter().collect::<Vec<i64>>(), vec![-1, 7]);
+}
@@ -0,0 +1,277 @@
+use super::{with_input, Budget, Input, State};
+use crate::{Error, Result};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Input,
+    Output,
+    /// The machine halted, and `value` is its result.
+    Halt,
+}
+
+/// A single value crossing a machine's I/O boundary. `time` is a logical clock shared by every
+/// machine writing to the same `Recorder`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    pub time: u64,
+    pub machine: usize,
+    pub direction: Direction,
+    pub value: i64,
+}
+
+/// Collects I/O events from any number of computers, possibly running on different threads.
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    events: Arc<Mutex<Vec<Event>>>,
+}
+
+impl Recorder {
+    pub fn new() -> Recorder {
+        Recorder::default()
+    }
+
+    pub(super) fn record(&self, machine: usize, direction: Direction, value: i64) {
+        let mut events = self.events.lock().unwrap();
+        let time = events.len() as u64;
+        events.push(Event {
+            time,
+            machine,
+            direction,
+            value,
+        });
+    }
+
+    pub fn log(&self) -> Log {
+        Log {
+            events: self.events.lock().unwrap().clone(),
+        }
+    }
+}
+
+/// A recorded session. The text form has one `<time> <machine> <in|out|halt> <value>` line per
+/// event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Log {
+    events: Vec<Event>,
+}
+
+impl Log {
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn values(&self, machine: usize, direction: Direction) -> Vec<i64> {
+        self.events
+            .iter()
+            .filter(|e| e.machine == machine && e.direction == direction)
+            .map(|e| e.value)
+            .collect()
+    }
+
+    fn machines(&self) -> Vec<usize> {
+        let mut machines: Vec<usize> = self.events.iter().map(|e| e.machine).collect();
+        machines.sort_unstable();
+        machines.dedup();
+        machines
+    }
+}
+
+impl fmt::Display for Log {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for e in &self.events {
+            let direction = match e.direction {
+                Direction::Input => "in",
+                Direction::Output => "out",
+                Direction::Halt => "halt",
+            };
+            writeln!(f, "{} {} {} {}", e.time, e.machine, direction, e.value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Log {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Log> {
+        let events = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(
+                |line| match line.split_whitespace().collect::<Vec<&str>>()[..] {
+                    [time, machine, direction, value] => Ok(Event {
+                        time: time.parse()?,
+                        machine: machine.parse()?,
+                        direction: match direction {
+                            "in" => Direction::Input,
+                            "out" => Direction::Output,
+                            "halt" => Direction::Halt,
+                            _ => return Err(Error::BadLogEntry(line.to_string())),
+                        },
+                        value: value.parse()?,
+                    }),
+                    _ => Err(Error::BadLogEntry(line.to_string())),
+                },
+            )
+            .collect::<Result<Vec<Event>>>()?;
+
+        Ok(Log { events })
+    }
+}
+
+/// Re-runs the machines in `log` together, taking its events in time order. Each event must be
+/// the next thing its machine does, given the inputs recorded before it, so every machine
+/// repeats the session in the recorded interleaving. Afterwards each machine must have ended as
+/// it did: halted with the same result if that was recorded, and otherwise waiting for input.
+/// `budget` bounds the work a machine may do to reach each event, and to reach its end state,
+/// so a machine that loops without I/O diverges instead of running forever.
+pub fn replay(initial_state: &[i64], log: &Log, budget: Budget) -> Result<()> {
+    let mut events = log.events.clone();
+    events.sort_by_key(|e| e.time);
+    if let Some(w) = events.windows(2).find(|w| w[0].time == w[1].time) {
+        return Err(Error::BadLogEntry(format!(
+            "two events at time {}",
+            w[0].time
+        )));
+    }
+
+    let recorder = Recorder::new();
+    let mut machines = BTreeMap::new();
+    for machine in log.machines() {
+        let (mut comp, output_rx) = with_input(initial_state, Input::Replay(VecDeque::new()));
+        comp.record(machine, recorder.clone());
+        machines.insert(machine, (comp, output_rx));
+    }
+    let recorded = || recorder.events.lock().unwrap().len();
+
+    for event in &events {
+        let (comp, _) = machines.get_mut(&event.machine).unwrap();
+        if let (Direction::Input, Input::Replay(values)) = (event.direction, &mut comp.io.input) {
+            values.push_back(event.value);
+        }
+
+        // Step until the machine does something that gets recorded.
+        let seen = recorded();
+        let deadline = budget.timeout.map(|limit| Instant::now() + limit);
+        let mut executed = 0;
+        while recorded() == seen {
+            if budget.is_exhausted(executed, deadline) {
+                return Err(Error::ReplayDiverged(event.machine));
+            }
+            match comp.run_with_budget(Budget::instructions(1)) {
+                State::BudgetExhausted | State::Halted(_) => {}
+                _ => return Err(Error::ReplayDiverged(event.machine)),
+            }
+            executed += 1;
+        }
+        let replayed = recorder.events.lock().unwrap()[seen];
+        if (replayed.direction, replayed.value) != (event.direction, event.value) {
+            return Err(Error::ReplayDiverged(event.machine));
+        }
+    }
+
+    for (&machine, (comp, _)) in &mut machines {
+        let halted = !log.values(machine, Direction::Halt).is_empty();
+        if halted {
+            continue;
+        }
+        let seen = recorded();
+        if comp.run_with_budget(budget) != State::InputExhausted || recorded() > seen {
+            return Err(Error::ReplayDiverged(machine));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+const BUDGET: Budget = Budget {
+    instructions: Some(1000),
+    timeout: None,
+};
+
+#[test]
+fn test_log_round_trip() {
+    let recorder = Recorder::new();
+    recorder.record(0, Direction::Input, 5);
+    recorder.record(0, Direction::Output, -12);
+    recorder.record(1, Direction::Input, -12);
+
+    let log = recorder.log();
+    let text = log.to_string();
+    assert_eq!(text, "0 0 in 5\n1 0 out -12\n2 1 in -12\n");
+    assert_eq!(text.parse::<Log>().unwrap(), log);
+    assert!("0 0 sideways 5".parse::<Log>().is_err());
+}
+
+#[test]
+fn test_replay() {
+    use crossbeam::crossbeam_channel::unbounded;
+
+    // Doubles its input.
+    let initial_state = vec![3, 9, 1002, 9, 2, 9, 4, 9, 99, 0];
+    let recorder = Recorder::new();
+    let (tx, rx) = unbounded();
+    let (mut comp, _output) = super::new(&initial_state, rx);
+    comp.record(3, recorder.clone());
+
+    tx.send(21).unwrap();
+    comp.run();
+
+    let log = recorder.log();
+    assert_eq!(log.values(3, Direction::Output), vec![42]);
+    assert_eq!(log.values(3, Direction::Halt), vec![3]);
+    assert!(replay(&initial_state, &log, BUDGET).is_ok());
+
+    // Triples its input instead.
+    let changed_state = vec![3, 9, 1002, 9, 3, 9, 4, 9, 99, 0];
+    assert!(replay(&changed_state, &log, BUDGET).is_err());
+
+    // A session that stopped before the machine halted, and one where it halted too early.
+    let unfinished: Log = "0 3 in 21\n1 3 out 42\n".parse().unwrap();
+    assert!(replay(&initial_state, &unfinished, BUDGET).is_err());
+    let early: Log = "0 3 in 21\n1 3 halt 3\n".parse().unwrap();
+    assert!(replay(&initial_state, &early, BUDGET).is_err());
+
+    // Echoes one value and then waits for another.
+    let waiting = vec![3, 9, 4, 9, 3, 9, 99, 0, 0, 0];
+    let unfinished: Log = "0 3 in 21\n1 3 out 21\n".parse().unwrap();
+    assert!(replay(&waiting, &unfinished, BUDGET).is_ok());
+}
+
+#[test]
+fn test_replay_interleaving() {
+    // Echoes its input and halts with the program's first cell.
+    let echo = vec![3, 9, 4, 9, 99, 0, 0, 0, 0, 0];
+    let log: Log = "0 0 in 5\n1 0 out 5\n2 1 in 5\n3 1 out 5\n4 0 halt 3\n5 1 halt 3\n"
+        .parse()
+        .unwrap();
+    assert!(replay(&echo, &log, BUDGET).is_ok());
+
+    // The same values for each machine, but machine 1 writes before it has read anything.
+    let log: Log = "0 1 out 5\n1 0 in 5\n2 0 out 5\n3 1 in 5\n4 0 halt 3\n5 1 halt 3\n"
+        .parse()
+        .unwrap();
+    assert!(replay(&echo, &log, BUDGET).is_err());
+
+    let log: Log = "0 0 in 5\n0 1 in 5\n".parse().unwrap();
+    assert!(replay(&echo, &log, BUDGET).is_err());
+}
+
+#[test]
+fn test_replay_runaway() {
+    // Loops forever without touching I/O, so never reaches the recorded input, or an end.
+    let runaway = vec![1105, 1, 0];
+    let log: Log = "0 0 in 5\n".parse().unwrap();
+    assert!(replay(&runaway, &log, BUDGET).is_err());
+
+    // Echoes its input and then loops forever.
+    let echo_then_loop = vec![3, 7, 4, 7, 1105, 1, 4, 0];
+    let log: Log = "0 0 in 5\n1 0 out 5\n".parse().unwrap();
+    assert!(replay(&echo_then_loop, &log, BUDGET).is_err());
+}
@@ -1,65 +1,16 @@
 extern crate nom;
-use std::error::Error as StdError;
-use std::fmt;
-use std::io;
-use std::num;
 
-#[derive(Debug)]
-pub enum Error {
-    IOError(io::Error),
-    ParseIntError(num::ParseIntError),
-    ToStringError(std::string::FromUtf8Error),
-    BadOpCode(i32),
-    BadParamMode(i32),
-    ExecError,
-    NomParseError,
-}
-
-impl StdError for Error {
-    fn description(&self) -> &str {
-        "description"
-    }
-}
-
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Error::IOError(err) => f.write_fmt(format_args!("IO error: {}", err)),
-            Error::ParseIntError(err) => f.write_fmt(format_args!("Parse error: {}", err)),
-            Error::ToStringError(err) => f.write_fmt(format_args!("ToString error: {}", err)),
-            Error::BadOpCode(code) => f.write_fmt(format_args!("Bad op code: {}", code)),
-            Error::BadParamMode(mode) => f.write_fmt(format_args!("Bad parameter mode: {}", mode)),
-            Error::ExecError => f.write_str("Exec error"),
-            Error::NomParseError => f.write_str("Parse error"),
-        }
-    }
-}
-
-impl From<io::Error> for Error {
-    fn from(err: io::Error) -> Error {
-        Error::IOError(err)
-    }
-}
-
-impl From<num::ParseIntError> for Error {
-    fn from(err: num::ParseIntError) -> Error {
-        Error::ParseIntError(err)
-    }
-}
-
-impl From<std::string::FromUtf8Error> for Error {
-    fn from(err: std::string::FromUtf8Error) -> Error {
-        Error::ToStringError(err)
-    }
-}
-
-impl<T> From<nom::Err<T>> for Error {
-    fn from(_err: nom::Err<T>) -> Error {
-        Error::NomParseError
-    }
-}
-
-pub type Result<T> = std::result::Result<T, Error>;
+mod error;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod intcode;
+pub mod ocr;
+pub mod parse;
+pub mod solution;
+
+pub use error::{Error, ParseError, Result};
+pub use solution::{Answer, Part, Solution};
 
 pub fn parse_i32(line: std::io::Result<String>) -> Result<i32> {
     let s = line?;
@@ -0,0 +1,313 @@
+//! Reads the block letters that some puzzles draw as their answer.
+//!
+//! Puzzles use two fonts: letters 6 pixels high, mostly 4 wide and 5 apart, and letters 10
+//! pixels high, 6 wide and 8 apart. The lit pixels are cropped to their bounds, whose height
+//! picks the font, and then letters are matched from the left, skipping blank columns between
+//! them. Not every letter of the alphabet has been seen in a puzzle, so only the known ones are
+//! recognised.
+
+use crate::grid::{Bounds, Grid, Point};
+use std::fmt;
+
+/// Letters 6 pixels high.
+const SMALL: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters 10 pixels high.
+const LARGE: &[(char, [&str; 10])] = &[
+    (
+        'A',
+        [
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        [
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        [
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        [
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        [
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        [
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        [
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'Z',
+        [
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// Nothing is lit.
+    Blank,
+    /// The lit pixels are this many rows high, which is neither font.
+    UnknownHeight(usize),
+    /// No letter matches the pixels starting at this column of the picture.
+    Unrecognised(usize),
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Blank => f.write_str("there are no letters to read"),
+            OcrError::UnknownHeight(height) => {
+                write!(f, "letters {} pixels high are in no known font", height)
+            }
+            OcrError::Unrecognised(column) => {
+                write!(f, "unrecognised letter at column {}", column)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+impl From<OcrError> for crate::Error {
+    fn from(err: OcrError) -> crate::Error {
+        crate::Error::Puzzle(Box::new(err))
+    }
+}
+
+/// A letter's lit pixels, with the blank columns on either side dropped.
+struct Glyph {
+    letter: char,
+    width: usize,
+    lit: Vec<Point>,
+}
+
+fn glyphs<const N: usize>(font: &[(char, [&str; N])]) -> Vec<Glyph> {
+    let mut glyphs: Vec<Glyph> = font
+        .iter()
+        .map(|&(letter, rows)| {
+            let pixels = rows.iter().enumerate().flat_map(|(y, row)| {
+                row.char_indices()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(x, _)| Point::new(x as i64, y as i64))
+            });
+            let lit: Vec<Point> = pixels.collect();
+            let left = lit.iter().map(|p| p.x).min().unwrap_or(0);
+            let right = lit.iter().map(|p| p.x).max().unwrap_or(0);
+            Glyph {
+                letter,
+                width: (right - left + 1) as usize,
+                lit: lit.iter().map(|p| Point::new(p.x - left, p.y)).collect(),
+            }
+        })
+        .collect();
+    // Wider letters first, so that none is read as a narrower one that fits inside it.
+    glyphs.sort_by_key(|glyph| std::cmp::Reverse(glyph.width));
+    glyphs
+}
+
+/// Reads the letters drawn by the cells of `picture` for which `lit` holds.
+pub fn read<T, F>(picture: &Grid<T>, lit: F) -> Result<String, OcrError>
+where
+    F: Fn(&T) -> bool,
+{
+    read_points(picture.positions(|cell| lit(cell)))
+}
+
+/// Reads the letters drawn by lit pixels at `points`, wherever they are.
+pub fn read_points<I: IntoIterator<Item = Point>>(points: I) -> Result<String, OcrError> {
+    let points: Vec<Point> = points.into_iter().collect();
+    let bounds = Bounds::around(points.iter().copied()).ok_or(OcrError::Blank)?;
+    let mut picture = Grid::new(bounds.width(), bounds.height(), false);
+    for &p in &points {
+        picture.set(Point::new(p.x - bounds.min.x, p.y - bounds.min.y), true);
+    }
+
+    let glyphs = match bounds.height() {
+        6 => glyphs(SMALL),
+        10 => glyphs(LARGE),
+        height => return Err(OcrError::UnknownHeight(height)),
+    };
+
+    let lit = |x: usize, y: usize| picture.get(Point::new(x as i64, y as i64)) == Some(&true);
+    let blank = |x: usize| (0..picture.height()).all(|y| !lit(x, y));
+    let matches = |glyph: &Glyph, left: usize| {
+        left + glyph.width <= picture.width()
+            && (0..glyph.width).all(|x| {
+                (0..picture.height()).all(|y| {
+                    let want = glyph.lit.contains(&Point::new(x as i64, y as i64));
+                    lit(left + x, y) == want
+                })
+            })
+    };
+
+    let mut text = String::new();
+    let mut x = 0;
+    while x < picture.width() {
+        if blank(x) {
+            x += 1;
+            continue;
+        }
+        let glyph = glyphs
+            .iter()
+            .find(|glyph| matches(glyph, x))
+            .ok_or(OcrError::Unrecognised(x))?;
+        text.push(glyph.letter);
+        x += glyph.width;
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+fn draw(rows: &[&str]) -> Grid<bool> {
+    Grid::parse(&rows.join("\n"), |c| Some(c == '#')).unwrap()
+}
+
+#[test]
+fn test_read() {
+    // Every small letter in a row, 5 pixels apart with a blank margin.
+    let mut rows = vec![String::from("."); 6];
+    for (_, glyph) in SMALL {
+        for (row, line) in rows.iter_mut().zip(glyph) {
+            row.push_str(&format!("{:.<5}", line));
+        }
+    }
+    let rows: Vec<&str> = rows.iter().map(String::as_str).collect();
+    assert_eq!(
+        read(&draw(&rows), |&lit| lit),
+        Ok("ABCEFGHIJKLOPRSUYZ".into())
+    );
+
+    // And the large ones 8 apart, with blank rows above and below.
+    let mut rows = vec![String::new(); 12];
+    for (_, glyph) in LARGE {
+        let blank = [""];
+        let lines = blank.iter().chain(glyph).chain(&blank);
+        for (row, line) in rows.iter_mut().zip(lines) {
+            row.push_str(&format!("{:.<8}", line));
+        }
+    }
+    let rows: Vec<&str> = rows.iter().map(String::as_str).collect();
+    let text = read(&draw(&rows), |&lit| lit);
+    assert_eq!(text, Ok("ABCEFGHJKLNPRXZ".into()));
+
+    // Letters can touch, and only the lit pixels' positions matter.
+    let touching = draw(&[
+        "#...#####",
+        "#...#...#",
+        ".#.#...#.",
+        "..#...#..",
+        "..#..#...",
+        "..#..####",
+    ]);
+    let points = touching
+        .positions(|&lit| lit)
+        .into_iter()
+        .map(|p| Point::new(p.x - 50, p.y + 7));
+    assert_eq!(read_points(points), Ok("YZ".into()));
+
+    assert_eq!(read(&draw(&["...."]), |&lit| lit), Err(OcrError::Blank));
+    assert_eq!(
+        read(&draw(&["#", "#"]), |&lit| lit),
+        Err(OcrError::UnknownHeight(2))
+    );
+    let smudged = draw(&[".##.", "#..#", "##.#", "####", "#..#", "#..#"]);
+    assert_eq!(read(&smudged, |&lit| lit), Err(OcrError::Unrecognised(0)));
+}
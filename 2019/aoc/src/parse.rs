@@ -0,0 +1,262 @@
+//! Readers for the usual shapes of puzzle input.
+//!
+//! Every reader reports failures as `Error::Parse`, positioned at the offending line (or
+//! value, or character) of the whole input, so they can be shown with `Error::in_file`.
+
+use crate::{Error, ParseError, Result};
+use nom::combinator::all_consuming;
+use nom::error::ErrorKind;
+use nom::IResult;
+use regex::{Captures, Regex};
+use std::str::FromStr;
+
+/// Parses every non-blank line, trimmed, as a `T`.
+pub fn lines<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<Error>,
+{
+    records(input, |line| line.parse().map_err(Into::into))
+}
+
+/// Parses every non-blank line, trimmed, with `parse`.
+pub fn records<T, F>(input: &str, parse: F) -> Result<Vec<T>>
+where
+    F: Fn(&str) -> Result<T>,
+{
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| parse(line).map_err(|err| err.locate(input, line)))
+        .collect()
+}
+
+/// Parses every non-blank line with a nom parser, which must consume the whole (trimmed) line.
+pub fn nom_records<'a, T, P>(input: &'a str, parser: P) -> Result<Vec<T>>
+where
+    P: Fn(&'a str) -> IResult<&'a str, T, (&'a str, ErrorKind)>,
+{
+    let parser = all_consuming(parser);
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            parser(line)
+                .map(|(_, record)| record)
+                .map_err(|err| Error::from(err).locate(input, line))
+        })
+        .collect()
+}
+
+/// Matches every non-blank line, trimmed, against `pattern` and builds a record from the
+/// captures. Lines that do not match are errors.
+pub fn regex_records<T, F>(input: &str, pattern: &Regex, build: F) -> Result<Vec<T>>
+where
+    F: Fn(&Captures) -> Result<T>,
+{
+    records(input, |line| match pattern.captures(line) {
+        Some(captures)
+            if captures
+                .get(0)
+                .is_some_and(|m| m.range() == (0..line.len())) =>
+        {
+            build(&captures)
+        }
+        _ => Err(ParseError::at(line, 0, format!("expected a line matching {}", pattern)).into()),
+    })
+}
+
+/// Parses comma separated values, each trimmed, ignoring a trailing newline.
+pub fn csv<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<Error>,
+{
+    input
+        .trim_end()
+        .split(',')
+        .map(str::trim)
+        .map(|val| {
+            val.parse()
+                .map_err(|err: T::Err| err.into().locate(input, val))
+        })
+        .collect()
+}
+
+/// Splits the input into blocks separated by blank lines and parses each with `parse`.
+pub fn blocks<T, F>(input: &str, parse: F) -> Result<Vec<T>>
+where
+    F: Fn(&str) -> Result<T>,
+{
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+
+    for line in input.split_inclusive('\n') {
+        let offset = line.as_ptr() as usize - input.as_ptr() as usize;
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end().len();
+        }
+    }
+    if let Some(start) = start {
+        blocks.push(&input[start..end]);
+    }
+
+    blocks
+        .into_iter()
+        .map(|block| parse(block).map_err(|err| err.locate(input, block)))
+        .collect()
+}
+
+/// Every character of the input with its `(x, y)` position, `y` counting lines from the top.
+pub fn cells(input: &str) -> impl Iterator<Item = ((usize, usize), char)> + '_ {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| line.chars().enumerate().map(move |(x, c)| ((x, y), c)))
+}
+
+/// Reads a rectangular grid of characters, row by row, converting each with `cell`. Characters
+/// that `cell` rejects and rows of the wrong length are errors. Blank lines at the end are
+/// ignored.
+pub fn grid<T, F>(input: &str, cell: F) -> Result<Vec<Vec<T>>>
+where
+    F: Fn(char) -> Option<T>,
+{
+    let rows: Vec<&str> = input.trim_end().lines().collect();
+    let width = rows.first().map_or(0, |row| row.chars().count());
+
+    rows.iter()
+        .map(|row| {
+            let parsed = row
+                .char_indices()
+                .map(|(i, c)| {
+                    cell(c).ok_or_else(|| {
+                        Error::from(ParseError::at(row, i, format!("unexpected {:?}", c)))
+                            .locate(input, row)
+                    })
+                })
+                .collect::<Result<Vec<T>>>()?;
+            if parsed.len() != width {
+                let message = format!("expected {} columns, found {}", width, parsed.len());
+                return Err(Error::from(ParseError::at(row, 0, message)).locate(input, row));
+            }
+            Ok(parsed)
+        })
+        .collect()
+}
+
+/// Reads a run of decimal digits, ignoring surrounding whitespace.
+pub fn digits(input: &str) -> Result<Vec<u8>> {
+    let trimmed = input.trim();
+    trimmed
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+                Error::from(ParseError::at(
+                    trimmed,
+                    i,
+                    format!("expected a digit, found {:?}", c),
+                ))
+                .locate(input, trimmed)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn position(err: Error) -> (usize, usize) {
+    match err {
+        Error::Parse(err) => (err.line(), err.column()),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_lines() {
+    assert_eq!(lines::<i32>("12\n -3\n\n7\n").unwrap(), vec![12, -3, 7]);
+    assert_eq!(position(lines::<i32>("12\n\n 3x\n").unwrap_err()), (3, 2));
+}
+
+#[test]
+fn test_records() {
+    use nom::character::complete::{alpha1, char, digit1};
+    use nom::sequence::separated_pair;
+
+    let orbits = records("COM)B\nB)C", |line| {
+        let mut bodies = line.split(')');
+        match (bodies.next(), bodies.next()) {
+            (Some(a), Some(b)) => Ok((a.to_string(), b.to_string())),
+            _ => Err(ParseError::at(line, 0, "expected A)B").into()),
+        }
+    });
+    assert_eq!(orbits.unwrap()[1], ("B".to_string(), "C".to_string()));
+
+    let pairs = nom_records("a=1\nb=2\n", |s| {
+        separated_pair(alpha1, char('='), digit1)(s)
+    });
+    assert_eq!(pairs.unwrap(), vec![("a", "1"), ("b", "2")]);
+    let err = nom_records("a=1\nb=2x\n", |s| {
+        separated_pair(alpha1, char('='), digit1)(s)
+    });
+    assert_eq!(position(err.unwrap_err()), (2, 4));
+
+    let pattern = Regex::new(r"#(\d+) @ (\d+),(\d+)").unwrap();
+    let claim = |c: &Captures| Ok((c[1].parse::<u32>()?, c[2].parse::<u32>()?));
+    assert_eq!(
+        regex_records("#1 @ 3,4\n#2 @ 5,6", &pattern, claim).unwrap(),
+        vec![(1, 3), (2, 5)]
+    );
+    let err = regex_records("#1 @ 3,4\n#2 @ 5,6 extra", &pattern, claim);
+    assert_eq!(position(err.unwrap_err()), (2, 1));
+}
+
+#[test]
+fn test_csv() {
+    assert_eq!(csv::<i64>("1, -2,3\n").unwrap(), vec![1, -2, 3]);
+    assert_eq!(position(csv::<i64>("1,2,x3").unwrap_err()), (1, 5));
+}
+
+#[test]
+fn test_blocks() {
+    let input = "1\n2\n\n\n3\n \n4\n5\n";
+    let sums = blocks(input, |block| Ok(lines::<i32>(block)?.iter().sum::<i32>()));
+    assert_eq!(sums.unwrap(), vec![3, 3, 9]);
+
+    let err = blocks("1\n\n2\nx", lines::<i32>).unwrap_err();
+    assert_eq!(position(err), (4, 1));
+}
+
+#[test]
+fn test_grid() {
+    let input = "#.\n.#\n";
+    assert_eq!(
+        cells(input).filter(|&(_, c)| c == '#').collect::<Vec<_>>(),
+        vec![((0, 0), '#'), ((1, 1), '#')]
+    );
+
+    let wall = |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    };
+    assert_eq!(
+        grid(input, wall).unwrap(),
+        vec![vec![true, false], vec![false, true]]
+    );
+    assert_eq!(position(grid("#.\n.x", wall).unwrap_err()), (2, 2));
+    assert_eq!(position(grid("#.\n.", wall).unwrap_err()), (2, 1));
+}
+
+#[test]
+fn test_digits() {
+    assert_eq!(digits(" 0123\n").unwrap(), vec![0, 1, 2, 3]);
+    assert_eq!(position(digits("01\n2").unwrap_err()), (1, 3));
+}
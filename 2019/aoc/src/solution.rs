@@ -0,0 +1,179 @@
+use crate::{Error, Result};
+use std::any::Any;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// A puzzle solution. `parse` turns the raw puzzle input into whatever both parts work on.
+pub trait Solution {
+    type Input: 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<Answer>;
+    fn part2(&self, input: &Self::Input) -> Result<Answer>;
+}
+
+/// The answer to one part of a puzzle. Most are numbers; the rest are text, such as a box ID or
+/// a message spelled out in a rendered image.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n.into())
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, u32);
+
+/// Integers that may not fit in an `i64`. Those that don't are kept as their decimal text, which
+/// prints and compares the same way a number would.
+macro_rules! answer_from_wide_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Number(n),
+                        Err(_) => Answer::Text(n.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from_wide_int!(isize, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn both() -> [Part; 2] {
+        [Part::One, Part::Two]
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => f.write_str("1"),
+            Part::Two => f.write_str("2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Part> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(Error::BadPart(s.to_string())),
+        }
+    }
+}
+
+/// A `Solution` with its input type erased, so that solutions for different days can be kept
+/// side by side. Implemented for every `Solution`.
+pub trait AnySolution: Sync {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer>;
+}
+
+impl<S> AnySolution for S
+where
+    S: Solution + Sync,
+{
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input parsed by a different solution");
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+}
+
+#[test]
+fn test_any_solution() {
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+
+        fn parse(&self, input: &str) -> Result<Vec<i32>> {
+            input.split_whitespace().map(|s| Ok(s.parse()?)).collect()
+        }
+
+        fn part1(&self, input: &Vec<i32>) -> Result<Answer> {
+            Ok(input.iter().sum::<i32>().into())
+        }
+
+        fn part2(&self, input: &Vec<i32>) -> Result<Answer> {
+            Ok(format!("x{}", input.iter().product::<i32>()).into())
+        }
+    }
+
+    let solution: &dyn AnySolution = &Sum;
+    let input = solution.parse("2 3 4").unwrap();
+    assert_eq!(
+        solution.solve(input.as_ref(), Part::One).unwrap(),
+        Answer::Number(9)
+    );
+    assert_eq!(
+        solution.solve(input.as_ref(), Part::Two).unwrap(),
+        Answer::Text("x24".to_string())
+    );
+    assert_eq!(Answer::from(24usize), Answer::Number(24));
+    assert_eq!(
+        Answer::from(u64::MAX),
+        Answer::Text("18446744073709551615".to_string())
+    );
+    assert_eq!(
+        Answer::from(10u128.pow(30)).to_string(),
+        format!("1{}", "0".repeat(30))
+    );
+    assert!(solution.parse("2 x").is_err());
+    assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+    assert!("3".parse::<Part>().is_err());
+}
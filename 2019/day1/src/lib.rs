@@ -0,0 +1,30 @@
+use aoc::Solution;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+
+    fn parse(&self, input: &str) -> aoc::Result<Vec<i32>> {
+        aoc::parse::lines(input)
+    }
+
+    fn part1(&self, masses: &Vec<i32>) -> aoc::Result<aoc::Answer> {
+        let total = masses.iter().fold(0, |acc, x| acc + (x / 3 - 2));
+        Ok(total.into())
+    }
+
+    fn part2(&self, masses: &Vec<i32>) -> aoc::Result<aoc::Answer> {
+        let total = masses.iter().fold(0, |acc, x| acc + calc_fuel(*x));
+        Ok(total.into())
+    }
+}
+
+fn calc_fuel(mass: i32) -> i32 {
+    let fuel = mass / 3 - 2;
+    if fuel <= 0 {
+        0
+    } else {
+        fuel + calc_fuel(fuel)
+    }
+}
@@ -0,0 +1,55 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day10::Asteroid;
+
+/// A `size` by `size` map with roughly `percent` of its positions holding an asteroid, the
+/// same every run.
+fn synthetic_map(size: i64, percent: u64) -> Vec<Asteroid> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut asteroids = Vec::new();
+    for y in 0..size {
+        for x in 0..size {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            if state % 100 < percent {
+                asteroids.push(Asteroid::new(x, y));
+            }
+        }
+    }
+    asteroids
+}
+
+fn bench_find_station(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find_station");
+    group.sample_size(10);
+    for &(size, percent) in &[(40, 30), (100, 30), (200, 10), (300, 5)] {
+        let asteroids = synthetic_map(size, percent);
+        let id =
+            BenchmarkId::from_parameter(format!("{0}x{0} {1} asteroids", size, asteroids.len()));
+        group.bench_with_input(id, &asteroids, |b, asteroids| {
+            b.iter(|| day10::find_station(asteroids))
+        });
+    }
+    group.finish();
+
+    // A direction set per asteroid, one asteroid after another, for comparison.
+    let mut group = c.benchmark_group("visible_from_each");
+    group.sample_size(10);
+    for &(size, percent) in &[(40, 30), (100, 30)] {
+        let asteroids = synthetic_map(size, percent);
+        let id =
+            BenchmarkId::from_parameter(format!("{0}x{0} {1} asteroids", size, asteroids.len()));
+        group.bench_with_input(id, &asteroids, |b, asteroids| {
+            b.iter(|| {
+                asteroids
+                    .iter()
+                    .map(|&a| day10::visible_from(a, asteroids))
+                    .max()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_find_station);
+criterion_main!(benches);
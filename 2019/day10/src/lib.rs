@@ -0,0 +1,268 @@
+use aoc::grid::{Grid, Point, Vector};
+use aoc::Solution;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+
+mod station;
+
+pub use station::{find_station, visibility, Station};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Asteroid>;
+
+    fn parse(&self, input: &str) -> aoc::Result<Vec<Asteroid>> {
+        read_map(input)
+    }
+
+    fn part1(&self, asteroids: &Vec<Asteroid>) -> aoc::Result<aoc::Answer> {
+        Ok(station(asteroids)?.visible.into())
+    }
+
+    fn part2(&self, asteroids: &Vec<Asteroid>) -> aoc::Result<aoc::Answer> {
+        let station = station(asteroids)?;
+        find_nth_asteroid(station.position, asteroids, 200)
+            .map(|asteroid| (asteroid.x * 100 + asteroid.y).into())
+            .ok_or_else(|| {
+                aoc::Error::ExecError("fewer than 200 asteroids to vaporize".to_string())
+            })
+    }
+}
+
+fn read_map(input: &str) -> aoc::Result<Vec<Asteroid>> {
+    let map = Grid::parse(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+
+    Ok(map.positions(|&asteroid| asteroid))
+}
+
+fn station(asteroids: &[Asteroid]) -> aoc::Result<Station> {
+    find_station(asteroids).ok_or_else(|| aoc::Error::ExecError("no asteroids".to_string()))
+}
+
+/// How many other asteroids `station` can see: one along each distinct direction.
+pub fn visible_from(station: Asteroid, asteroids: &[Asteroid]) -> usize {
+    asteroids
+        .iter()
+        .filter(|&&a| a != station)
+        .map(|&a| Gradient::new(station, a))
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// The other asteroids in the order a laser at `station` destroys them: it starts pointing up
+/// and turns clockwise, destroying only the nearest asteroid in each direction on each turn.
+pub fn vaporization_order(station: Asteroid, asteroids: &[Asteroid]) -> Vec<Asteroid> {
+    let mut lines: BTreeMap<Gradient, Vec<Asteroid>> = BTreeMap::new();
+    for &a in asteroids.iter().filter(|&&a| a != station) {
+        lines.entry(Gradient::new(station, a)).or_default().push(a);
+    }
+    // Farthest first, so that each turn can pop the nearest.
+    let mut lines: Vec<Vec<Asteroid>> = lines.into_values().collect();
+    for line in &mut lines {
+        line.sort_by_key(|&a| std::cmp::Reverse(a.manhattan(station)));
+    }
+
+    let mut order = Vec::with_capacity(asteroids.len());
+    while !lines.is_empty() {
+        order.extend(lines.iter_mut().filter_map(Vec::pop));
+        lines.retain(|line| !line.is_empty());
+    }
+    order
+}
+
+pub fn find_nth_asteroid(station: Asteroid, asteroids: &[Asteroid], n: usize) -> Option<Asteroid> {
+    vaporization_order(station, asteroids)
+        .get(n.checked_sub(1)?)
+        .copied()
+}
+
+/// The direction from one asteroid to another, reduced so that asteroids hiding behind each
+/// other share it. Directions order clockwise from straight up, remembering that `y` grows
+/// downwards.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+struct Gradient(Vector);
+
+impl Gradient {
+    fn new(from: Asteroid, to: Asteroid) -> Gradient {
+        Gradient((to - from).reduced())
+    }
+
+    /// 0 from straight up until just before straight down, 1 from there on.
+    fn half(&self) -> u8 {
+        let Vector { x, y } = self.0;
+        if x > 0 || (x == 0 && y < 0) {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl Ord for Gradient {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Within a half, `other` is clockwise of `self` exactly when their cross product is
+        // positive, and it can only be zero when they point the same way.
+        let (a, b) = (self.0, other.0);
+        let cross = a.x * b.y - a.y * b.x;
+        self.half().cmp(&other.half()).then(0.cmp(&cross))
+    }
+}
+
+impl PartialOrd for Gradient {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub type Asteroid = Point;
+
+#[cfg(test)]
+const LARGE_EXAMPLE: &str = "\
+.#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##";
+
+#[test]
+fn test_find_station() {
+    let examples = [
+        (".#..#\n.....\n#####\n....#\n...##", (3, 4), 8),
+        (
+            "......#.#.\n#..#.#....\n..#######.\n.#.#.###..\n.#..#.....\n\
+             ..#....#.#\n#..#....#.\n.##.#..###\n##...#..#.\n.#....####",
+            (5, 8),
+            33,
+        ),
+        (
+            "#.#...#.#.\n.###....#.\n.#....#...\n##.#.#.#.#\n....#.#.#.\n\
+             .##..###.#\n..#...##..\n..##....##\n......#...\n.####.###.",
+            (1, 2),
+            35,
+        ),
+        (
+            ".#..#..###\n####.###.#\n....###.#.\n..###.##.#\n##.##.#.#.\n\
+             ....###..#\n..#.#..#.#\n#..#.#.###\n.##...##.#\n.....#.#..",
+            (6, 3),
+            41,
+        ),
+        (LARGE_EXAMPLE, (11, 13), 210),
+    ];
+    for &(map, (x, y), visible) in &examples {
+        let asteroids = Day10.parse(map).unwrap();
+        let station = find_station(&asteroids).unwrap();
+        assert_eq!(
+            (station.position, station.visible),
+            (Point::new(x, y), visible)
+        );
+        for &(asteroid, visible) in &visibility(&asteroids) {
+            assert_eq!(visible, visible_from(asteroid, &asteroids));
+        }
+    }
+
+    let asteroids = Day10.parse(examples[0].0).unwrap();
+    let counts: Vec<usize> = visibility(&asteroids).into_iter().map(|(_, n)| n).collect();
+    assert_eq!(counts, vec![7, 7, 6, 7, 7, 7, 5, 7, 8, 7]);
+    let ranking = find_station(&asteroids).unwrap().ranking;
+    assert_eq!(ranking[..2], [(Point::new(3, 4), 8), (Point::new(1, 0), 7)]);
+    assert_eq!(ranking.last(), Some(&(Point::new(4, 2), 5)));
+    assert!(find_station(&[]).is_none());
+    assert_eq!(find_station(&[Point::ORIGIN]).unwrap().visible, 0);
+}
+
+#[test]
+fn test_vaporization_order() {
+    let map = "\
+.#....#####...#..
+##...##.#####..##
+##...#...#.#####.
+..#.....#...###..
+..#.#.....#....##";
+    let asteroids = Day10.parse(map).unwrap();
+    let order = vaporization_order(Point::new(8, 3), &asteroids);
+    let first: Vec<(i64, i64)> = order[..9].iter().map(|a| (a.x, a.y)).collect();
+    assert_eq!(
+        first,
+        vec![
+            (8, 1),
+            (9, 0),
+            (9, 1),
+            (10, 0),
+            (9, 2),
+            (11, 1),
+            (12, 1),
+            (11, 2),
+            (15, 1)
+        ]
+    );
+    assert_eq!(order.len(), asteroids.len() - 1);
+
+    let asteroids = Day10.parse(LARGE_EXAMPLE).unwrap();
+    let station = Point::new(11, 13);
+    let order = vaporization_order(station, &asteroids);
+    for &(n, x, y) in &[
+        (1, 11, 12),
+        (2, 12, 1),
+        (3, 12, 2),
+        (10, 12, 8),
+        (20, 16, 0),
+    ] {
+        assert_eq!(order[n - 1], Point::new(x, y), "asteroid {}", n);
+    }
+    for &(n, x, y) in &[
+        (50, 16, 9),
+        (100, 10, 16),
+        (199, 9, 6),
+        (201, 10, 9),
+        (299, 11, 1),
+    ] {
+        assert_eq!(order[n - 1], Point::new(x, y), "asteroid {}", n);
+    }
+    assert_eq!(order.len(), 299);
+    assert_eq!(Day10.part2(&asteroids).unwrap(), aoc::Answer::from(802));
+    assert_eq!(find_nth_asteroid(station, &asteroids, 0), None);
+}
+
+#[test]
+fn test_gradient_order() {
+    let directions = [
+        (0, -1),
+        (1, -3),
+        (1, -1),
+        (1, 0),
+        (2, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -2),
+    ];
+    let mut gradients: Vec<Gradient> = directions
+        .iter()
+        .map(|&(x, y)| Gradient::new(Point::ORIGIN, Point::new(x * 3, y * 3)))
+        .collect();
+    let expected = gradients.clone();
+    gradients.reverse();
+    gradients.sort();
+    assert_eq!(gradients, expected);
+    assert_eq!(gradients[2].0, Vector::new(1, -1));
+}
@@ -0,0 +1,84 @@
+//! Finds the best place for a monitoring station.
+//!
+//! Two asteroids see each other exactly when nothing lies on the line between them, so each pair
+//! is checked once and counts for both. Checking a pair walks the lattice points between them on
+//! a map of occupied positions, and the pairs are shared out between threads by the asteroid
+//! they start from.
+
+use crate::Asteroid;
+use aoc::grid::{Bounds, Grid};
+use rayon::prelude::*;
+
+/// The best asteroid for a station and how every asteroid compares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Station {
+    pub position: Asteroid,
+    /// How many asteroids can be seen from `position`.
+    pub visible: usize,
+    /// Every asteroid with how many others it can see, most first. Ties keep the order the
+    /// asteroids were given in, and `position` is the first entry.
+    pub ranking: Vec<(Asteroid, usize)>,
+}
+
+/// Every asteroid with how many others it can see, in the order given.
+pub fn visibility(asteroids: &[Asteroid]) -> Vec<(Asteroid, usize)> {
+    let bounds = match Bounds::around(asteroids.iter().copied()) {
+        Some(bounds) => bounds,
+        None => return Vec::new(),
+    };
+    let offset = bounds.min - Asteroid::ORIGIN;
+    let mut occupied = Grid::new(bounds.width(), bounds.height(), false);
+    for &a in asteroids {
+        occupied.set(a - offset, true);
+    }
+    let blocked = |a: Asteroid, b: Asteroid| {
+        let step = (b - a).reduced();
+        let mut p = a + step;
+        while p != b {
+            if occupied.get(p - offset) == Some(&true) {
+                return true;
+            }
+            p += step;
+        }
+        false
+    };
+
+    let n = asteroids.len();
+    let counts = (0..n)
+        .into_par_iter()
+        .fold(
+            || vec![0; n],
+            |mut counts, i| {
+                for j in i + 1..n {
+                    if asteroids[i] != asteroids[j] && !blocked(asteroids[i], asteroids[j]) {
+                        counts[i] += 1;
+                        counts[j] += 1;
+                    }
+                }
+                counts
+            },
+        )
+        .reduce(
+            || vec![0; n],
+            |mut total, counts| {
+                for (t, c) in total.iter_mut().zip(counts) {
+                    *t += c;
+                }
+                total
+            },
+        );
+
+    asteroids.iter().copied().zip(counts).collect()
+}
+
+/// The asteroid that can see the most others, or `None` if there are no asteroids.
+pub fn find_station(asteroids: &[Asteroid]) -> Option<Station> {
+    let mut ranking = visibility(asteroids);
+    ranking.sort_by_key(|&(_, visible)| std::cmp::Reverse(visible));
+    let &(position, visible) = ranking.first()?;
+    Some(Station {
+        position,
+        visible,
+        ranking,
+    })
+}
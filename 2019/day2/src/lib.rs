@@ -0,0 +1,92 @@
+use aoc::intcode::{self, instructions::InstructionSet, Budget, State};
+use aoc::Solution;
+use crossbeam::crossbeam_channel::unbounded;
+
+// Generous upper bound on the instructions any noun/verb candidate may take.
+const MAX_INSTRUCTIONS: u64 = 10_000;
+
+pub const TARGET: i64 = 19690720;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<i64>;
+
+    fn parse(&self, input: &str) -> aoc::Result<Vec<i64>> {
+        intcode::parse_program(input)
+    }
+
+    fn part1(&self, initial_state: &Vec<i64>) -> aoc::Result<aoc::Answer> {
+        Ok(run(initial_state, 12, 2)?.into())
+    }
+
+    fn part2(&self, initial_state: &Vec<i64>) -> aoc::Result<aoc::Answer> {
+        let (noun, verb) = find_inputs(initial_state, TARGET)?
+            .ok_or_else(|| aoc::Error::ExecError(format!("no inputs produce {}", TARGET)))?;
+        Ok((100 * noun + verb).into())
+    }
+}
+
+/// The first noun and verb for which the program produces `target`. Candidates that don't halt
+/// within budget, or hit a bad opcode, are not a match.
+pub fn find_inputs(initial_state: &[i64], target: i64) -> aoc::Result<Option<(i64, i64)>> {
+    for noun in 0..100 {
+        for verb in 0..100 {
+            if execute(&patch(initial_state, noun, verb)?) == State::Halted(target) {
+                return Ok(Some((noun, verb)));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn run(initial_state: &[i64], noun: i64, verb: i64) -> aoc::Result<i64> {
+    match execute(&patch(initial_state, noun, verb)?) {
+        State::Halted(result) => Ok(result),
+        State::BadOpCode(code) => Err(aoc::Error::BadOpCode(code)),
+        state => Err(aoc::Error::ExecError(format!(
+            "noun {}, verb {}: stopped with {:?}",
+            noun, verb, state
+        ))),
+    }
+}
+
+/// The program with `noun` and `verb` in addresses 1 and 2.
+fn patch(initial_state: &[i64], noun: i64, verb: i64) -> aoc::Result<Vec<i64>> {
+    if initial_state.len() < 3 {
+        return Err(aoc::Error::ExecError(format!(
+            "a program of {} cells has no room for a noun and verb",
+            initial_state.len()
+        )));
+    }
+    let mut memory = initial_state.to_vec();
+    memory[1] = noun;
+    memory[2] = verb;
+    Ok(memory)
+}
+
+fn execute(memory: &[i64]) -> State {
+    let (_tx, rx) = unbounded();
+    let (mut computer, _output) = intcode::new(memory, rx);
+    computer.set_instructions(InstructionSet::day2());
+    computer.run_with_budget(Budget::instructions(MAX_INSTRUCTIONS))
+}
+
+#[test]
+fn test_run() {
+    let initial_state = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+    assert_eq!(run(&initial_state, 9, 10).unwrap(), 3500);
+    assert!(run(&[1, 0, 0, 0, 3, 0, 99], 0, 0).is_err());
+    assert!(run(&[99, 0], 0, 0).is_err());
+    assert!(find_inputs(&[99], 0).is_err());
+}
+
+#[test]
+fn test_find_inputs() {
+    // Writes memory[noun] * memory[verb] over the halt and runs it, so only a product of 99
+    // halts, and every other candidate before it is a bad opcode.
+    let initial_state = vec![2, 0, 0, 4, 99, 3];
+    assert_eq!(find_inputs(&initial_state, 2).unwrap(), Some((1, 4)));
+    assert_eq!(find_inputs(&initial_state, 3).unwrap(), None);
+}
@@ -0,0 +1,278 @@
+extern crate nom;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, one_of},
+    combinator::{all_consuming, map, map_res},
+    multi::separated_list,
+    sequence::tuple,
+    IResult,
+};
+
+use aoc::grid::{Point, Vector};
+use aoc::Solution;
+use std::str::FromStr;
+
+pub mod render;
+mod sweep;
+
+pub use sweep::{find_intersections, Intersection};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Wire>;
+
+    fn parse(&self, input: &str) -> aoc::Result<Vec<Wire>> {
+        aoc::parse::lines(input)
+    }
+
+    fn part1(&self, wires: &Vec<Wire>) -> aoc::Result<aoc::Answer> {
+        Ok(intersections(wires)?.0.into())
+    }
+
+    fn part2(&self, wires: &Vec<Wire>) -> aoc::Result<aoc::Answer> {
+        Ok(intersections(wires)?.1.into())
+    }
+}
+
+/// Distance from the origin of the closest intersection, and the fewest combined steps to reach
+/// an intersection, between any two of the wires.
+pub fn intersections(wires: &[Wire]) -> aoc::Result<(i64, i64)> {
+    let found = find_intersections(wires);
+    let closest = found.iter().map(Intersection::distance).min();
+    let shortest = found.iter().map(Intersection::combined_steps).min();
+    closest
+        .zip(shortest)
+        .ok_or_else(|| aoc::Error::ExecError("no two wires cross".to_string()))
+}
+
+/// One leg of a wire: which way it runs and how far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Right(usize),
+    Left(usize),
+    Up(usize),
+    Down(usize),
+}
+
+impl Direction {
+    /// A single step this way, and how many of them to take. Up increases `y`.
+    fn steps(self) -> (Vector, i64) {
+        match self {
+            Direction::Right(n) => (Vector::new(1, 0), n as i64),
+            Direction::Left(n) => (Vector::new(-1, 0), n as i64),
+            Direction::Up(n) => (Vector::new(0, 1), n as i64),
+            Direction::Down(n) => (Vector::new(0, -1), n as i64),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Equation {
+    X(i64),
+    Y(i64),
+}
+
+#[derive(Debug, PartialEq)]
+struct Segment {
+    equation: Equation,
+    start: Point,
+    end: Point,
+}
+
+impl Segment {
+    fn length(&self) -> i64 {
+        match self.equation {
+            Equation::X(_) => (self.start.y - self.end.y).abs(),
+            Equation::Y(_) => (self.start.x - self.end.x).abs(),
+        }
+    }
+
+    fn length_at(&self, point: &Point) -> i64 {
+        match self.equation {
+            Equation::X(_) => (self.start.y - point.y).abs(),
+            Equation::Y(_) => (self.start.x - point.x).abs(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Wire(Vec<Segment>);
+
+impl Wire {
+    /// The wire laid out from the origin, one segment per direction.
+    pub fn from_directions(directions: &[Direction]) -> Wire {
+        let mut at = Point::ORIGIN;
+        let segments = directions
+            .iter()
+            .map(|direction| {
+                let (step, n) = direction.steps();
+                let equation = if step.x == 0 {
+                    Equation::X(at.x)
+                } else {
+                    Equation::Y(at.y)
+                };
+                let segment = Segment {
+                    equation,
+                    start: at,
+                    end: at + step * n,
+                };
+                at = segment.end;
+                segment
+            })
+            .collect();
+
+        Wire(segments)
+    }
+
+    /// Every lattice point along the wire with the number of steps taken to reach it, from the
+    /// origin at 0 steps. A point the wire passes more than once comes up each time.
+    pub fn points(&self) -> impl Iterator<Item = (Point, i64)> + '_ {
+        let legs = self.0.iter().scan(0, |steps, segment| {
+            let before = *steps;
+            *steps += segment.length();
+            Some((segment, before))
+        });
+        let along = legs.flat_map(|(segment, before)| {
+            let step = (segment.end - segment.start).reduced();
+            (1..=segment.length()).map(move |i| (segment.start + step * i, before + i))
+        });
+        std::iter::once((Point::ORIGIN, 0)).chain(along)
+    }
+}
+
+impl FromStr for Wire {
+    type Err = aoc::Error;
+
+    fn from_str(s: &str) -> aoc::Result<Wire> {
+        let trimmed = s.trim();
+        all_consuming(read_wire)(trimmed)
+            .map(|(_, wire)| wire)
+            .map_err(|err| aoc::Error::from(err).locate(s, trimmed))
+    }
+}
+
+fn read_direction(input: &str) -> IResult<&str, Direction> {
+    let (input, (d, a)) = tuple((one_of("RLUD"), map_res(digit1, to_usize)))(input)?;
+    let direction = match d {
+        'R' => Direction::Right(a),
+        'L' => Direction::Left(a),
+        'U' => Direction::Up(a),
+        'D' => Direction::Down(a),
+        _ => unreachable!(),
+    };
+
+    Ok((input, direction))
+}
+
+fn read_wire(input: &str) -> IResult<&str, Wire> {
+    map(separated_list(tag(","), read_direction), |directions| {
+        Wire::from_directions(&directions)
+    })(input)
+}
+
+fn to_usize(input: &str) -> Result<usize, std::num::ParseIntError> {
+    input.parse::<usize>()
+}
+
+#[cfg(test)]
+fn wires(input: &str) -> Vec<Wire> {
+    Day3.parse(input).unwrap()
+}
+
+#[test]
+fn test_intersections() {
+    let found = find_intersections(&wires("R8,U5,L5,D3\nU7,R6,D4,L4"));
+    let points: Vec<_> = found.iter().map(|i| (i.point.x, i.point.y)).collect();
+    assert_eq!(points, vec![(3, 3), (6, 5)]);
+    assert_eq!(found[0].steps, (20, 20));
+    assert_eq!(
+        intersections(&wires("R8,U5,L5,D3\nU7,R6,D4,L4")).unwrap(),
+        (6, 30)
+    );
+    assert!(intersections(&wires("R2\nL2")).is_err());
+}
+
+#[test]
+fn test_collinear_and_many_wires() {
+    // The second wire doubles back along the first.
+    let found = find_intersections(&wires("R8\nL2,R5"));
+    let steps: Vec<_> = found.iter().map(|i| (i.point.x, i.steps)).collect();
+    assert_eq!(steps, vec![(1, (1, 5)), (2, (2, 6)), (3, (3, 7))]);
+
+    // Vertical overlaps, and a wire passing the same point twice keeps its fewest steps.
+    let found = find_intersections(&wires("U5\nR1,U2,L1,U2,D1"));
+    let steps: Vec<_> = found.iter().map(|i| (i.point.y, i.steps)).collect();
+    assert_eq!(steps, vec![(2, (2, 4)), (3, (3, 5)), (4, (4, 6))]);
+
+    let found = find_intersections(&wires("R8\nL2,R5\nU1,R1,D2"));
+    assert_eq!(found.len(), 5);
+    let at_one: Vec<_> = found
+        .iter()
+        .filter(|i| i.point == Point::new(1, 0))
+        .map(|i| (i.wires, i.steps))
+        .collect();
+    assert_eq!(
+        at_one,
+        vec![((0, 1), (1, 5)), ((0, 2), (1, 3)), ((1, 2), (5, 3))]
+    );
+}
+
+#[test]
+fn test_read_direction() {
+    assert_eq!(read_direction("R345"), Ok(("", Direction::Right(345))));
+}
+
+#[test]
+fn test_read_wire() {
+    let wire: Wire = "R1,L2,U3,D4".parse().unwrap();
+    assert_eq!(
+        wire,
+        Wire::from_directions(&[
+            Direction::Right(1),
+            Direction::Left(2),
+            Direction::Up(3),
+            Direction::Down(4),
+        ])
+    );
+    assert_eq!(wire.0.len(), 4);
+    assert_eq!(wire.0[1].end, Point::new(-1, 0));
+    assert_eq!(wire.0[3].end, Point::new(-1, -1));
+
+    assert_eq!(" R1 ".parse::<Wire>().unwrap().0.len(), 1);
+    for bad in &["R1,X2", "R1,", "R-1"] {
+        assert!(bad.parse::<Wire>().is_err(), "{:?} parsed", bad);
+    }
+}
+
+#[test]
+fn test_points() {
+    let wire: Wire = "R2,U1,L1".parse().unwrap();
+    let points: Vec<_> = wire.points().map(|(p, steps)| (p.x, p.y, steps)).collect();
+    assert_eq!(
+        points,
+        vec![(0, 0, 0), (1, 0, 1), (2, 0, 2), (2, 1, 3), (1, 1, 4)]
+    );
+    assert_eq!(Wire::from_directions(&[]).points().count(), 1);
+}
+
+#[test]
+fn test_examples() {
+    let examples = [
+        ("R8,U5,L5,D3\nU7,R6,D4,L4", 6, 30),
+        (
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83",
+            159,
+            610,
+        ),
+        (
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+            135,
+            410,
+        ),
+    ];
+    for &(input, distance, steps) in &examples {
+        assert_eq!(intersections(&wires(input)).unwrap(), (distance, steps));
+    }
+}
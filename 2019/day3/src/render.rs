@@ -0,0 +1,219 @@
+//! Pictures of the wires: SVG for a browser and coloured text for a terminal.
+//!
+//! Both draw with up at the top, as the puzzle's `U` increases `y`. Every intersection is
+//! marked, and the closest one (by distance from the origin) and the shortest one (by combined
+//! steps) stand out.
+
+use crate::{find_intersections, Intersection, Wire};
+use aoc::grid::{Bounds, Grid, Point, Vector};
+use std::fmt::Write;
+
+const COLOURS: [&str; 6] = [
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4",
+];
+
+/// SGR codes for the terminal versions of `COLOURS`.
+const ANSI_COLOURS: [u8; 6] = [31, 32, 34, 33, 35, 36];
+
+/// The corners of every wire and the origin.
+fn corners(wire: &Wire) -> impl Iterator<Item = Point> + '_ {
+    std::iter::once(Point::ORIGIN).chain(wire.0.iter().map(|segment| segment.end))
+}
+
+/// The intersections nearest the origin and with fewest combined steps.
+fn highlights(found: &[Intersection]) -> (Option<&Intersection>, Option<&Intersection>) {
+    (
+        found.iter().min_by_key(|i| i.distance()),
+        found.iter().min_by_key(|i| i.combined_steps()),
+    )
+}
+
+pub fn svg(wires: &[Wire]) -> String {
+    let found = find_intersections(wires);
+    let (closest, shortest) = highlights(&found);
+    let bounds = Bounds::around(wires.iter().flat_map(corners)).unwrap_or(Bounds {
+        min: Point::ORIGIN,
+        max: Point::ORIGIN,
+    });
+
+    let size = bounds.width().max(bounds.height()) as i64;
+    let stroke = (size / 400).max(1);
+    let margin = stroke * 10;
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+        bounds.min.x - margin,
+        -bounds.max.y - margin,
+        bounds.width() as i64 + 2 * margin,
+        bounds.height() as i64 + 2 * margin
+    )
+    .unwrap();
+
+    for (i, wire) in wires.iter().enumerate() {
+        let points: Vec<String> = corners(wire).map(|p| format!("{},{}", p.x, -p.y)).collect();
+        writeln!(
+            svg,
+            r#"  <polyline class="wire" points="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            points.join(" "),
+            COLOURS[i % COLOURS.len()],
+            stroke
+        )
+        .unwrap();
+    }
+
+    let circle = |svg: &mut String, p: Point, r: i64, style: &str, title: &str| {
+        writeln!(
+            svg,
+            r#"  <circle cx="{}" cy="{}" r="{}" {}><title>{}</title></circle>"#,
+            p.x, -p.y, r, style, title
+        )
+        .unwrap();
+    };
+    for i in &found {
+        let title = format!(
+            "({}, {}): wires {} and {}, {} + {} steps",
+            i.point.x, i.point.y, i.wires.0, i.wires.1, i.steps.0, i.steps.1
+        );
+        circle(&mut svg, i.point, stroke * 2, r#"class="crossing""#, &title);
+    }
+    if let Some(i) = closest {
+        let title = format!("closest: distance {}", i.distance());
+        let style = r#"class="closest" fill="none" stroke="gold""#;
+        circle(&mut svg, i.point, stroke * 6, style, &title);
+    }
+    if let Some(i) = shortest {
+        let title = format!("shortest: {} steps", i.combined_steps());
+        let style = r#"class="shortest" fill="none" stroke="magenta""#;
+        circle(&mut svg, i.point, stroke * 8, style, &title);
+    }
+    circle(
+        &mut svg,
+        Point::ORIGIN,
+        stroke * 3,
+        r#"class="origin""#,
+        "origin",
+    );
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cell {
+    Empty,
+    Wire(usize),
+    Crossing,
+    Closest,
+    Shortest,
+    Origin,
+}
+
+/// The wires squeezed into `width` by `height` characters, coloured with ANSI escapes. Several
+/// points can share a character, so a character shows the first wire to reach it.
+pub fn ansi(wires: &[Wire], width: usize, height: usize) -> String {
+    let found = find_intersections(wires);
+    let (closest, shortest) = highlights(&found);
+    let bounds = match Bounds::around(wires.iter().flat_map(corners)) {
+        Some(bounds) if width > 0 && height > 0 => bounds,
+        _ => return String::new(),
+    };
+
+    // Scales so that the bounds' corners land on the canvas' corners, with up at the top.
+    let scale = |p: Point| {
+        let along = |from: i64, span: usize, cells: usize| {
+            from * (cells as i64 - 1) / (span as i64 - 1).max(1)
+        };
+        let offset = Vector::new(p.x - bounds.min.x, bounds.max.y - p.y);
+        Point::new(
+            along(offset.x, bounds.width(), width),
+            along(offset.y, bounds.height(), height),
+        )
+    };
+
+    let mut canvas = Grid::new(width, height, Cell::Empty);
+    for (i, wire) in wires.iter().enumerate() {
+        for (p, _) in wire.points() {
+            if let Some(cell @ Cell::Empty) = canvas.get_mut(scale(p)) {
+                *cell = Cell::Wire(i);
+            }
+        }
+    }
+    for i in &found {
+        canvas.set(scale(i.point), Cell::Crossing);
+    }
+    if let Some(i) = closest {
+        canvas.set(scale(i.point), Cell::Closest);
+    }
+    if let Some(i) = shortest {
+        canvas.set(scale(i.point), Cell::Shortest);
+    }
+    canvas.set(scale(Point::ORIGIN), Cell::Origin);
+
+    let mut out = String::new();
+    for row in canvas.rows() {
+        for cell in row {
+            match cell {
+                Cell::Empty => out.push(' '),
+                Cell::Wire(i) => write!(
+                    out,
+                    "\x1b[{}m#\x1b[0m",
+                    ANSI_COLOURS[i % ANSI_COLOURS.len()]
+                )
+                .unwrap(),
+                Cell::Crossing => out.push_str("\x1b[1mx\x1b[0m"),
+                Cell::Closest => out.push_str("\x1b[1;7mC\x1b[0m"),
+                Cell::Shortest => out.push_str("\x1b[1;7mS\x1b[0m"),
+                Cell::Origin => out.push_str("\x1b[1mo\x1b[0m"),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+fn strip_escapes(s: &str) -> String {
+    let mut plain = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+#[test]
+fn test_render() {
+    use aoc::Solution;
+
+    let wires = crate::Day3.parse("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap();
+
+    let svg = svg(&wires);
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert_eq!(svg.matches(r#"class="crossing""#).count(), 2);
+    assert!(svg.contains(r#"points="0,0 8,0 8,-5 3,-5 3,-2""#));
+    assert!(svg.contains("closest: distance 6"));
+    assert!(svg.contains("shortest: 30 steps"));
+
+    let text = ansi(&wires, 9, 8);
+    assert!(text.contains("\x1b[31m#"));
+    assert_eq!(
+        strip_escapes(&text),
+        concat!(
+            "#######  \n",
+            "#     #  \n",
+            "#  ###S##\n",
+            "#  #  # #\n",
+            "# #C### #\n",
+            "#  #    #\n",
+            "#       #\n",
+            "o########\n",
+        )
+    );
+    assert_eq!(ansi(&wires, 0, 5), "");
+}
@@ -0,0 +1,180 @@
+//! Finds where wires meet without comparing every pair of segments.
+//!
+//! Horizontal and vertical segments can only cross at one point, found by sweeping a vertical
+//! line from left to right: horizontal segments are active while the line is over them and each
+//! vertical segment looks up the active ones within its span. Segments along the same line can
+//! overlap for a stretch instead, so those are sorted along each line and compared only while
+//! their spans overlap; every lattice point they share is an intersection.
+
+use crate::{Equation, Segment, Wire};
+use aoc::grid::Point;
+use std::collections::{BTreeMap, HashMap};
+
+/// A point where two wires meet, with the fewest steps each takes to reach it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Intersection {
+    pub point: Point,
+    /// Indices of the two wires, the lower first.
+    pub wires: (usize, usize),
+    /// Steps along each wire, in the same order as `wires`.
+    pub steps: (i64, i64),
+}
+
+impl Intersection {
+    /// Manhattan distance from the origin.
+    pub fn distance(&self) -> i64 {
+        self.point.manhattan(Point::ORIGIN)
+    }
+
+    pub fn combined_steps(&self) -> i64 {
+        self.steps.0 + self.steps.1
+    }
+}
+
+/// A segment with the wire it belongs to and how far along the wire it starts.
+struct Placed<'a> {
+    wire: usize,
+    steps: i64,
+    segment: &'a Segment,
+}
+
+impl Placed<'_> {
+    fn steps_to(&self, point: &Point) -> i64 {
+        self.steps + self.segment.length_at(point)
+    }
+}
+
+/// Fewest steps along each wire, keyed by where and which wires meet.
+#[derive(Default)]
+struct Found(HashMap<(Point, usize, usize), (i64, i64)>);
+
+impl Found {
+    fn record(&mut self, point: Point, a: &Placed, b: &Placed) {
+        if point == Point::ORIGIN || a.wire == b.wire {
+            return;
+        }
+
+        let (a, b) = if a.wire < b.wire { (a, b) } else { (b, a) };
+        let steps = (a.steps_to(&point), b.steps_to(&point));
+        let best = self.0.entry((point, a.wire, b.wire)).or_insert(steps);
+        *best = (best.0.min(steps.0), best.1.min(steps.1));
+    }
+}
+
+/// Every point where two of the wires meet, other than the origin, sorted by point.
+pub fn find_intersections(wires: &[Wire]) -> Vec<Intersection> {
+    let mut horizontal = Vec::new();
+    let mut vertical = Vec::new();
+    for (wire, w) in wires.iter().enumerate() {
+        let mut steps = 0;
+        for segment in &w.0 {
+            let placed = Placed {
+                wire,
+                steps,
+                segment,
+            };
+            match segment.equation {
+                Equation::Y(_) => horizontal.push(placed),
+                Equation::X(_) => vertical.push(placed),
+            }
+            steps += segment.length();
+        }
+    }
+
+    let mut found = Found::default();
+    crossings(&horizontal, &vertical, &mut found);
+    overlaps(&mut horizontal, |p| (p.y, p.x), &mut found);
+    overlaps(&mut vertical, |p| (p.x, p.y), &mut found);
+
+    let mut intersections: Vec<Intersection> = found
+        .0
+        .into_iter()
+        .map(|((point, a, b), steps)| Intersection {
+            point,
+            wires: (a, b),
+            steps,
+        })
+        .collect();
+    intersections.sort_by_key(|i| (i.point, i.wires));
+    intersections
+}
+
+fn span(from: i64, to: i64) -> (i64, i64) {
+    (from.min(to), from.max(to))
+}
+
+/// Where horizontal segments cross vertical ones.
+fn crossings(horizontal: &[Placed], vertical: &[Placed], found: &mut Found) {
+    // At the same x, horizontals are added before the verticals look for them and removed
+    // after, as segments include their ends.
+    const ADD: u8 = 0;
+    const QUERY: u8 = 1;
+    const REMOVE: u8 = 2;
+
+    let mut events = Vec::with_capacity(horizontal.len() * 2 + vertical.len());
+    for (i, h) in horizontal.iter().enumerate() {
+        let (left, right) = span(h.segment.start.x, h.segment.end.x);
+        events.push((left, ADD, i));
+        events.push((right, REMOVE, i));
+    }
+    for (i, v) in vertical.iter().enumerate() {
+        events.push((v.segment.start.x, QUERY, i));
+    }
+    events.sort_unstable();
+
+    let mut active: BTreeMap<i64, Vec<usize>> = BTreeMap::new();
+    for (x, kind, i) in events {
+        match kind {
+            ADD => active
+                .entry(horizontal[i].segment.start.y)
+                .or_default()
+                .push(i),
+            REMOVE => {
+                let y = horizontal[i].segment.start.y;
+                if let Some(row) = active.get_mut(&y) {
+                    row.retain(|&h| h != i);
+                    if row.is_empty() {
+                        active.remove(&y);
+                    }
+                }
+            }
+            _ => {
+                let v = &vertical[i];
+                let (bottom, top) = span(v.segment.start.y, v.segment.end.y);
+                for (&y, row) in active.range(bottom..=top) {
+                    for &h in row {
+                        found.record(Point::new(x, y), &horizontal[h], v);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Where segments along the same line overlap. `axes` splits a point into the coordinate that
+/// picks the line and the one along it.
+fn overlaps<F>(segments: &mut [Placed], axes: F, found: &mut Found)
+where
+    F: Fn(Point) -> (i64, i64),
+{
+    let key = |p: &Placed| {
+        let (line, from) = axes(p.segment.start);
+        let (lo, hi) = span(from, axes(p.segment.end).1);
+        (line, lo, hi)
+    };
+    segments.sort_by_key(key);
+
+    let mut active: Vec<(i64, i64, usize)> = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        let (line, lo, hi) = key(segment);
+        active.retain(|&(other_line, other_hi, _)| other_line == line && other_hi >= lo);
+        for &(_, other_hi, j) in &active {
+            for along in lo..=hi.min(other_hi) {
+                // `axes` swaps coordinates or leaves them, so applying it again undoes it.
+                let (x, y) = axes(Point::new(line, along));
+                found.record(Point::new(x, y), &segments[j], segment);
+            }
+        }
+        active.push((line, hi, i));
+    }
+}
@@ -0,0 +1,283 @@
+//! Counting passwords digit by digit without listing them.
+//!
+//! Whether the rest of a password can pass the rules depends only on a summary of the digits
+//! before it: the last digit, how long its run is so far, and which `SomeRun` rules an earlier
+//! run already met. Once a prefix has left the range's bounds behind, every prefix with the same
+//! summary and the same number of digits still to come has the same count, so that count is
+//! worked out once and reused. A range of any width then takes time proportional to its width.
+//!
+//! Counts are `u128`s, which holds every count for numbers of up to 38 digits. Wider ranges can
+//! have more passwords than that, and counting them fails with `TooMany` instead.
+
+use crate::rules::{Rule, Rules, RunLength};
+use crate::Range;
+use std::collections::HashMap;
+use std::fmt;
+
+/// More passwords pass than a `u128` can count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooMany;
+
+impl fmt::Display for TooMany {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("too many passwords to count")
+    }
+}
+
+impl std::error::Error for TooMany {}
+
+impl From<TooMany> for aoc::Error {
+    fn from(err: TooMany) -> aoc::Error {
+        aoc::Error::Puzzle(Box::new(err))
+    }
+}
+
+/// What the rules need to know about a prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    last: Option<u8>,
+    /// The length of the last run, capped where longer runs stop making a difference.
+    run: usize,
+    /// Bit `i` is set once a finished run has met the `i`th `SomeRun` rule.
+    met: u64,
+}
+
+struct Counter {
+    non_decreasing: bool,
+    forbidden: [bool; 10],
+    some_runs: Vec<RunLength>,
+    every_runs: Vec<RunLength>,
+    cap: usize,
+    /// Counts keyed by the digits still to come and the state before them.
+    memo: HashMap<(usize, State), u128>,
+}
+
+impl Counter {
+    fn new(rules: &Rules) -> Counter {
+        let mut counter = Counter {
+            non_decreasing: false,
+            forbidden: [false; 10],
+            some_runs: Vec::new(),
+            every_runs: Vec::new(),
+            cap: 1,
+            memo: HashMap::new(),
+        };
+        for rule in rules.rules() {
+            match rule {
+                Rule::NonDecreasing => counter.non_decreasing = true,
+                Rule::SomeRun(len) => counter.some_runs.push(*len),
+                Rule::EveryRun(len) => counter.every_runs.push(*len),
+                Rule::Forbidden(digits) => {
+                    for &d in digits.iter().filter(|&&d| d < 10) {
+                        counter.forbidden[d as usize] = true;
+                    }
+                }
+                // Handled by only counting the lengths it allows.
+                Rule::Length(_) => {}
+            }
+        }
+        assert!(counter.some_runs.len() <= 64, "too many SomeRun rules");
+
+        let bounds = counter.some_runs.iter().chain(&counter.every_runs);
+        counter.cap = bounds
+            .flat_map(|len| vec![len.min(), len.max()])
+            .filter(|&n| n != usize::MAX)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        counter
+    }
+
+    /// Records a run that has just ended, or `None` if it breaks an `EveryRun` rule.
+    fn finish_run(&self, state: State) -> Option<State> {
+        if state.last.is_none() {
+            return Some(state);
+        }
+        if !self.every_runs.iter().all(|len| len.allows(state.run)) {
+            return None;
+        }
+        let met = self
+            .some_runs
+            .iter()
+            .enumerate()
+            .fold(state.met, |met, (i, len)| {
+                if len.allows(state.run) {
+                    met | 1 << i
+                } else {
+                    met
+                }
+            });
+        Some(State { met, ..state })
+    }
+
+    /// The state after appending `digit`, or `None` if nothing starting that way can pass.
+    fn step(&self, state: State, digit: u8) -> Option<State> {
+        if self.forbidden[digit as usize] {
+            return None;
+        }
+
+        let next = match state.last {
+            Some(last) if self.non_decreasing && digit < last => return None,
+            Some(last) if digit == last => State {
+                run: (state.run + 1).min(self.cap),
+                ..state
+            },
+            _ => State {
+                last: Some(digit),
+                run: 1,
+                ..self.finish_run(state)?
+            },
+        };
+
+        if self.every_runs.iter().any(|len| next.run > len.max()) {
+            None
+        } else {
+            Some(next)
+        }
+    }
+
+    fn accepts(&self, state: State) -> bool {
+        let all = if self.some_runs.len() == 64 {
+            u64::MAX
+        } else {
+            (1 << self.some_runs.len()) - 1
+        };
+        state.last.is_some() && self.finish_run(state).is_some_and(|s| s.met == all)
+    }
+
+    /// Numbers between `lo` and `hi`, which have the same length, whose digits from `pos` on
+    /// follow a prefix summed up by `state`. `at_lo` and `at_hi` say whether that prefix matches
+    /// the bound.
+    fn count(
+        &mut self,
+        (lo, hi): (&[u8], &[u8]),
+        pos: usize,
+        state: State,
+        at_lo: bool,
+        at_hi: bool,
+    ) -> Result<u128, TooMany> {
+        if pos == lo.len() {
+            return Ok(self.accepts(state) as u128);
+        }
+
+        let free = !at_lo && !at_hi;
+        let key = (lo.len() - pos, state);
+        if free {
+            if let Some(&count) = self.memo.get(&key) {
+                return Ok(count);
+            }
+        }
+
+        let from = if at_lo { lo[pos] } else { 0 };
+        let to = if at_hi { hi[pos] } else { 9 };
+        let mut count: u128 = 0;
+        for d in from..=to {
+            if let Some(next) = self.step(state, d) {
+                let rest = self.count(
+                    (lo, hi),
+                    pos + 1,
+                    next,
+                    at_lo && d == from,
+                    at_hi && d == to,
+                )?;
+                count = count.checked_add(rest).ok_or(TooMany)?;
+            }
+        }
+
+        if free {
+            self.memo.insert(key, count);
+        }
+        Ok(count)
+    }
+}
+
+/// How many numbers in `range` pass `rules`, without trying them one by one.
+pub fn count_passwords(range: &Range, rules: &Rules) -> Result<u128, TooMany> {
+    let mut counter = Counter::new(rules);
+    let start = State {
+        last: None,
+        run: 0,
+        met: 0,
+    };
+    (range.start().len()..=range.end().len())
+        .filter(|&len| rules.allows_length(len))
+        .filter_map(|len| range.with_length(len))
+        .try_fold(0u128, |total, (lo, hi)| {
+            let count = counter.count((&lo, &hi), 0, start, true, true)?;
+            total.checked_add(count).ok_or(TooMany)
+        })
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+#[cfg(test)]
+fn run_length() -> impl Strategy<Value = RunLength> {
+    prop_oneof![
+        (1usize..5).prop_map(RunLength::Exactly),
+        (1usize..5).prop_map(RunLength::AtLeast),
+        (1usize..5).prop_map(RunLength::AtMost),
+    ]
+}
+
+#[cfg(test)]
+fn rule() -> impl Strategy<Value = Rule> {
+    prop_oneof![
+        Just(Rule::NonDecreasing),
+        run_length().prop_map(Rule::SomeRun),
+        run_length().prop_map(Rule::EveryRun),
+        proptest::collection::vec(0u8..10, 1..3).prop_map(Rule::Forbidden),
+        (1usize..7).prop_map(Rule::Length),
+    ]
+}
+
+#[cfg(test)]
+fn digits(n: u64) -> Vec<u8> {
+    n.to_string().bytes().map(|b| b - b'0').collect()
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn prop_matches_enumeration(
+        start in 0u64..200_000,
+        width in 0u64..20_000,
+        rules in proptest::collection::vec(rule(), 0..4),
+    ) {
+        let rules = rules.into_iter().fold(Rules::new(), Rules::and);
+        let range = Range::new(&digits(start), &digits(start + width));
+        prop_assert_eq!(
+            count_passwords(&range, &rules),
+            Ok(crate::count_valid_combinations(&range, &rules) as u128)
+        );
+    }
+}
+
+#[test]
+fn test_count_passwords() {
+    let range = Range::parse("100000-999999").unwrap();
+    assert_eq!(
+        count_passwords(&range, &crate::part1_rules()),
+        Ok(crate::count_valid_combinations(&range, &crate::part1_rules()) as u128)
+    );
+
+    // Non-decreasing numbers with up to 15 digits can't have a 0, leaving C(15 + 9, 9) - 1
+    // multisets of 1 to 9.
+    let range = Range::parse("1-999999999999999").unwrap();
+    let rules = Rules::new().non_decreasing();
+    assert_eq!(count_passwords(&range, &rules), Ok(1_307_503));
+
+    // Every 40 digit number with no digit repeated next to itself, after a first digit that
+    // can't be 0.
+    let mut lo = vec![0; 40];
+    lo[0] = 1;
+    let range = Range::new(&lo, &[9; 40]);
+    let rules = Rules::new().every_run(RunLength::Exactly(1));
+    assert_eq!(count_passwords(&range, &rules), Ok(9u128.pow(40)));
+
+    // Every number of up to 38 digits still fits, but adding the 39 digit ones doesn't.
+    let range = Range::new(&[0], &[9; 38]);
+    assert_eq!(count_passwords(&range, &Rules::new()), Ok(10u128.pow(38)));
+    let range = Range::new(&[0], &[9; 39]);
+    assert_eq!(count_passwords(&range, &Rules::new()), Err(TooMany));
+}
@@ -0,0 +1,205 @@
+use aoc::{ParseError, Solution};
+use rules::{Rules, RunLength};
+use std::cmp::Ordering;
+
+mod count;
+pub mod rules;
+
+pub use count::{count_passwords, TooMany};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    /// The inclusive range of candidate passwords.
+    type Input = Range;
+
+    fn parse(&self, input: &str) -> aoc::Result<Range> {
+        Range::parse(input)
+    }
+
+    fn part1(&self, range: &Range) -> aoc::Result<aoc::Answer> {
+        Ok(count_passwords(range, &part1_rules())?.into())
+    }
+
+    fn part2(&self, range: &Range) -> aoc::Result<aoc::Answer> {
+        Ok(count_passwords(range, &part2_rules())?.into())
+    }
+}
+
+/// Six digits that never decrease, with at least two adjacent digits the same.
+pub fn part1_rules() -> Rules {
+    Rules::new()
+        .length(6)
+        .non_decreasing()
+        .some_run(RunLength::AtLeast(2))
+}
+
+/// As for part 1, but some pair of adjacent equal digits must not be part of a longer run.
+pub fn part2_rules() -> Rules {
+    Rules::new()
+        .length(6)
+        .non_decreasing()
+        .some_run(RunLength::Exactly(2))
+}
+
+/// An inclusive range of numbers, kept as decimal digits so that it can be as wide as needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range {
+    start: Vec<u8>,
+    end: Vec<u8>,
+}
+
+impl Range {
+    pub fn new(start: &[u8], end: &[u8]) -> Range {
+        Range {
+            start: without_leading_zeros(start),
+            end: without_leading_zeros(end),
+        }
+    }
+
+    /// Reads `start-end`.
+    pub fn parse(input: &str) -> aoc::Result<Range> {
+        let trimmed = input.trim();
+        let dash = trimmed.find('-').ok_or_else(|| {
+            aoc::Error::from(ParseError::at(trimmed, 0, "expected a range like 123-456"))
+                .locate(input, trimmed)
+        })?;
+        let (start, end) = (&trimmed[..dash], &trimmed[dash + 1..]);
+        let digits = |bound: &str| {
+            if bound.is_empty() {
+                let err = ParseError::at(bound, 0, "expected a number");
+                return Err(aoc::Error::from(err).locate(input, bound));
+            }
+            aoc::parse::digits(bound).map_err(|err| err.locate(input, bound))
+        };
+        Ok(Range::new(&digits(start)?, &digits(end)?))
+    }
+
+    pub fn start(&self) -> &[u8] {
+        &self.start
+    }
+
+    pub fn end(&self) -> &[u8] {
+        &self.end
+    }
+
+    /// The bounds of the numbers in the range with `len` digits, if there are any.
+    pub(crate) fn with_length(&self, len: usize) -> Option<(Vec<u8>, Vec<u8>)> {
+        let lo = if len == self.start.len() {
+            self.start.clone()
+        } else if len > self.start.len() {
+            let mut lo = vec![0; len];
+            lo[0] = 1;
+            lo
+        } else {
+            return None;
+        };
+        let hi = match len.cmp(&self.end.len()) {
+            Ordering::Equal => self.end.clone(),
+            Ordering::Less => vec![9; len],
+            Ordering::Greater => return None,
+        };
+        if lo <= hi {
+            Some((lo, hi))
+        } else {
+            None
+        }
+    }
+}
+
+fn without_leading_zeros(digits: &[u8]) -> Vec<u8> {
+    let first = digits.iter().position(|&d| d != 0);
+    match first {
+        Some(i) => digits[i..].to_vec(),
+        None => vec![0],
+    }
+}
+
+/// How many numbers in `range` pass `rules`, trying digits from the left and abandoning any
+/// prefix the rules already rule out.
+pub fn count_valid_combinations(range: &Range, rules: &Rules) -> u64 {
+    (range.start.len()..=range.end.len())
+        .filter(|&len| rules.allows_length(len))
+        .filter_map(|len| range.with_length(len))
+        .map(|(lo, hi)| count_between(&lo, &hi, rules, &mut Vec::new(), true, true))
+        .sum()
+}
+
+/// Numbers between `lo` and `hi`, which have the same length, that start with `prefix` and pass
+/// `rules`. `at_lo` and `at_hi` say whether the prefix so far matches that bound.
+fn count_between(
+    lo: &[u8],
+    hi: &[u8],
+    rules: &Rules,
+    prefix: &mut Vec<u8>,
+    at_lo: bool,
+    at_hi: bool,
+) -> u64 {
+    let pos = prefix.len();
+    if pos == lo.len() {
+        return rules.check(prefix) as u64;
+    }
+
+    let from = if at_lo { lo[pos] } else { 0 };
+    let to = if at_hi { hi[pos] } else { 9 };
+    let mut count = 0;
+    for d in from..=to {
+        prefix.push(d);
+        if rules.allows_prefix(prefix) {
+            count += count_between(lo, hi, rules, prefix, at_lo && d == from, at_hi && d == to);
+        }
+        prefix.pop();
+    }
+    count
+}
+
+#[cfg(test)]
+fn brute_force(start: u64, end: u64, rules: &Rules) -> u64 {
+    (start..=end)
+        .filter(|n| {
+            let digits: Vec<u8> = n.to_string().bytes().map(|b| b - b'0').collect();
+            rules.check(&digits)
+        })
+        .count() as u64
+}
+
+#[test]
+fn test_parse_range() {
+    let range = Day4.parse("0123-4567\n").unwrap();
+    assert_eq!(
+        (range.start(), range.end()),
+        (&[1, 2, 3][..], &[4, 5, 6, 7][..])
+    );
+    for bad in &["123", "-5", "12-", "1x-5"] {
+        assert!(Day4.parse(bad).is_err(), "{:?} parsed", bad);
+    }
+}
+
+#[test]
+fn test_counts() {
+    let range = Range::parse("9-100000").unwrap();
+    let rules = Rules::new()
+        .non_decreasing()
+        .some_run(RunLength::AtLeast(2));
+    assert_eq!(
+        count_valid_combinations(&range, &rules),
+        brute_force(9, 100_000, &rules)
+    );
+
+    let rules = Rules::new().every_run(RunLength::AtMost(2)).forbid(&[3, 5]);
+    assert_eq!(
+        count_valid_combinations(&Range::parse("950-12345").unwrap(), &rules),
+        brute_force(950, 12345, &rules)
+    );
+
+    assert_eq!(
+        count_valid_combinations(&Range::parse("500-400").unwrap(), &rules),
+        0
+    );
+
+    // Ranges wider than any integer type still work.
+    let wide = Range::parse("11111111111111111111111111-11111111111111111111111112").unwrap();
+    assert_eq!(count_valid_combinations(&wide, &part1_rules()), 0);
+    let rules = Rules::new().non_decreasing().length(26);
+    assert_eq!(count_valid_combinations(&wide, &rules), 2);
+}
@@ -0,0 +1,182 @@
+//! Password rules that can be combined, and checked against a partial password so that
+//! searches can give up on a prefix early.
+
+/// How long a run of equal adjacent digits may be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunLength {
+    Exactly(usize),
+    AtLeast(usize),
+    AtMost(usize),
+}
+
+impl RunLength {
+    pub(crate) fn min(self) -> usize {
+        match self {
+            RunLength::Exactly(n) | RunLength::AtLeast(n) => n,
+            RunLength::AtMost(_) => 1,
+        }
+    }
+
+    pub(crate) fn max(self) -> usize {
+        match self {
+            RunLength::Exactly(n) | RunLength::AtMost(n) => n,
+            RunLength::AtLeast(_) => usize::MAX,
+        }
+    }
+
+    pub fn allows(self, len: usize) -> bool {
+        (self.min()..=self.max()).contains(&len)
+    }
+}
+
+/// A single condition on a password's digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// Digits never decrease from left to right.
+    NonDecreasing,
+    /// At least one run of equal adjacent digits has this length.
+    SomeRun(RunLength),
+    /// Every run of equal adjacent digits has this length, counting single digits as runs of 1.
+    EveryRun(RunLength),
+    /// None of these digits appear.
+    Forbidden(Vec<u8>),
+    /// Exactly this many digits.
+    Length(usize),
+}
+
+/// Lengths of the runs of equal adjacent digits, left to right.
+pub(crate) fn runs(digits: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    let mut rest = digits;
+    std::iter::from_fn(move || {
+        let first = *rest.first()?;
+        let len = rest.iter().take_while(|&&d| d == first).count();
+        rest = &rest[len..];
+        Some(len)
+    })
+}
+
+impl Rule {
+    pub fn check(&self, digits: &[u8]) -> bool {
+        match self {
+            Rule::NonDecreasing => digits.windows(2).all(|w| w[0] <= w[1]),
+            Rule::SomeRun(len) => runs(digits).any(|run| len.allows(run)),
+            Rule::EveryRun(len) => runs(digits).all(|run| len.allows(run)),
+            Rule::Forbidden(forbidden) => digits.iter().all(|d| !forbidden.contains(d)),
+            Rule::Length(n) => digits.len() == *n,
+        }
+    }
+
+    /// Whether some password starting with `prefix` could still pass.
+    pub fn allows_prefix(&self, prefix: &[u8]) -> bool {
+        match self {
+            Rule::SomeRun(_) => true,
+            Rule::EveryRun(len) => {
+                // The last run may still grow, so it can only be too long already.
+                let runs: Vec<usize> = runs(prefix).collect();
+                match runs.split_last() {
+                    Some((&last, done)) => {
+                        last <= len.max() && done.iter().all(|&run| len.allows(run))
+                    }
+                    None => true,
+                }
+            }
+            Rule::Length(n) => prefix.len() <= *n,
+            rule => rule.check(prefix),
+        }
+    }
+
+    /// Whether a password of `len` digits could pass.
+    pub fn allows_length(&self, len: usize) -> bool {
+        match self {
+            Rule::Length(n) => len == *n,
+            Rule::SomeRun(run) => len >= run.min(),
+            _ => true,
+        }
+    }
+}
+
+/// Rules that must all hold, built up one at a time, as in
+/// `Rules::new().non_decreasing().some_run(RunLength::AtLeast(2))`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rules {
+    rules: Vec<Rule>,
+}
+
+impl Rules {
+    pub fn new() -> Rules {
+        Rules::default()
+    }
+
+    pub fn and(mut self, rule: Rule) -> Rules {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn non_decreasing(self) -> Rules {
+        self.and(Rule::NonDecreasing)
+    }
+
+    pub fn some_run(self, len: RunLength) -> Rules {
+        self.and(Rule::SomeRun(len))
+    }
+
+    pub fn every_run(self, len: RunLength) -> Rules {
+        self.and(Rule::EveryRun(len))
+    }
+
+    pub fn forbid(self, digits: &[u8]) -> Rules {
+        self.and(Rule::Forbidden(digits.to_vec()))
+    }
+
+    pub fn length(self, n: usize) -> Rules {
+        self.and(Rule::Length(n))
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub fn check(&self, digits: &[u8]) -> bool {
+        self.rules.iter().all(|rule| rule.check(digits))
+    }
+
+    pub fn allows_prefix(&self, prefix: &[u8]) -> bool {
+        self.rules.iter().all(|rule| rule.allows_prefix(prefix))
+    }
+
+    pub fn allows_length(&self, len: usize) -> bool {
+        self.rules.iter().all(|rule| rule.allows_length(len))
+    }
+}
+
+#[test]
+fn test_rules() {
+    let part1 = Rules::new()
+        .non_decreasing()
+        .some_run(RunLength::AtLeast(2));
+    assert!(part1.check(&[1, 1, 1, 1, 1, 1]));
+    assert!(!part1.check(&[2, 2, 3, 4, 5, 0]));
+    assert!(!part1.check(&[1, 2, 3, 7, 8, 9]));
+
+    let part2 = Rules::new()
+        .non_decreasing()
+        .some_run(RunLength::Exactly(2));
+    assert!(part2.check(&[1, 1, 2, 2, 3, 3]));
+    assert!(!part2.check(&[1, 2, 3, 4, 4, 4]));
+    assert!(part2.check(&[1, 1, 1, 1, 2, 2]));
+
+    assert_eq!(runs(&[1, 1, 2, 3, 3, 3]).collect::<Vec<_>>(), vec![2, 1, 3]);
+    assert!(Rule::EveryRun(RunLength::AtMost(2)).check(&[1, 1, 2, 1, 1]));
+    assert!(!Rule::EveryRun(RunLength::AtMost(2)).check(&[1, 1, 1]));
+    assert!(!Rules::new().forbid(&[0, 7]).check(&[1, 7]));
+    assert!(!Rules::new().length(3).check(&[1, 2]));
+
+    // Prefixes are only rejected once no continuation could pass.
+    let every_pair = Rule::EveryRun(RunLength::Exactly(2));
+    assert!(every_pair.allows_prefix(&[1, 1, 2]));
+    assert!(!every_pair.allows_prefix(&[1, 2, 2]));
+    assert!(!every_pair.allows_prefix(&[1, 1, 1]));
+    assert!(!Rule::NonDecreasing.allows_prefix(&[3, 2]));
+    assert!(Rule::SomeRun(RunLength::Exactly(2)).allows_prefix(&[1, 2, 3]));
+    assert!(!part2.allows_length(1));
+}
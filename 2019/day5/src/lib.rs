@@ -0,0 +1,71 @@
+use aoc::intcode::{self, instructions::InstructionSet, Budget, State};
+use aoc::Solution;
+use crossbeam::crossbeam_channel::unbounded;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<i64>;
+
+    fn parse(&self, input: &str) -> aoc::Result<Vec<i64>> {
+        intcode::parse_program(input)
+    }
+
+    fn part1(&self, initial_state: &Vec<i64>) -> aoc::Result<aoc::Answer> {
+        diagnostic_code(initial_state, 1)
+    }
+
+    fn part2(&self, initial_state: &Vec<i64>) -> aoc::Result<aoc::Answer> {
+        diagnostic_code(initial_state, 5)
+    }
+}
+
+/// The last output of a diagnostic run, which follows the results of any tests.
+fn diagnostic_code(initial_state: &[i64], system_id: i64) -> aoc::Result<aoc::Answer> {
+    run_diagnostic(initial_state, system_id)?
+        .last()
+        .map(|&code| code.into())
+        .ok_or_else(|| {
+            aoc::Error::ExecError("the diagnostic program produced no output".to_string())
+        })
+}
+
+/// Everything the diagnostic program outputs when given `system_id`.
+pub fn run_diagnostic(initial_state: &[i64], system_id: i64) -> aoc::Result<Vec<i64>> {
+    let (tx, rx) = unbounded();
+    let (mut comp, output) = intcode::new(initial_state, rx);
+    comp.set_instructions(InstructionSet::day5());
+
+    tx.send(system_id).unwrap();
+    drop(tx);
+    match comp.run_with_budget(Budget::unlimited()) {
+        State::Halted(_) => {}
+        State::BadOpCode(code) => return Err(aoc::Error::BadOpCode(code)),
+        State::InputExhausted => {
+            let msg = "the diagnostic program wanted more than the system ID";
+            return Err(aoc::Error::ExecError(msg.to_string()));
+        }
+        state => {
+            let msg = format!("the diagnostic program stopped with {:?}", state);
+            return Err(aoc::Error::ExecError(msg));
+        }
+    }
+    drop(comp);
+
+    Ok(output.iter().collect())
+}
+
+#[test]
+fn test_compare_to_eight() {
+    let initial_state = vec![
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+
+    assert_eq!(run_diagnostic(&initial_state, 7).unwrap(), vec![999]);
+    assert_eq!(run_diagnostic(&initial_state, 8).unwrap(), vec![1000]);
+    assert_eq!(run_diagnostic(&initial_state, 9).unwrap(), vec![1001]);
+    assert!(run_diagnostic(&[1105, 1, 3, 42], 1).is_err());
+    assert!(run_diagnostic(&[3, 0, 3, 0, 99], 1).is_err());
+}
@@ -1,7 +1,9 @@
-mod computer;
+use aoc::Solution;
+use day5::{run_diagnostic, Day5};
 
 fn main() -> aoc::Result<()> {
-    let mut comp = computer::load("input")?;
-    println!("Result: {}", comp.run());
+    let initial_state = Day5.parse(&aoc::input::read(2019, 5)?)?;
+    println!("Part1: {:?}", run_diagnostic(&initial_state, 1)?);
+    println!("Part2: {:?}", run_diagnostic(&initial_state, 5)?);
     Ok(())
 }
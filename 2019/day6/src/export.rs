@@ -0,0 +1,100 @@
+//! The orbit tree in formats other tools can draw.
+
+use crate::{OrbitError, OrbitMap};
+use serde::Serialize;
+
+/// A Graphviz digraph with an edge from each body to its satellites.
+pub fn to_dot(map: &OrbitMap) -> Result<String, OrbitError> {
+    let mut dot = String::from("digraph orbits {\n");
+    for (center, satellite) in map.orbits() {
+        dot.push_str(&format!(
+            "    {} -> {};\n",
+            quote(center)?,
+            quote(satellite)?
+        ));
+    }
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+/// A DOT quoted ID. Inside quotes DOT only treats `\"` as an escape, leaving any other
+/// backslash as it is, so only quotes are escaped. That leaves no way to end an ID with a
+/// backslash, which would escape the closing quote.
+fn quote(name: &str) -> Result<String, OrbitError> {
+    if name.ends_with('\\') {
+        return Err(OrbitError::Unquotable(name.to_string()));
+    }
+    Ok(format!("\"{}\"", name.replace('"', "\\\"")))
+}
+
+#[derive(Serialize)]
+struct Body<'a> {
+    name: &'a str,
+    center: Option<&'a str>,
+    depth: usize,
+}
+
+/// A flat JSON array of bodies, each with the body it orbits (`null` for `COM`) and its depth,
+/// outwards from `COM`. Flat rather than nested so that deep maps stay cheap to write and read.
+pub fn to_json(map: &OrbitMap) -> String {
+    let com = Body {
+        name: "COM",
+        center: None,
+        depth: 0,
+    };
+    let bodies: Vec<Body> = std::iter::once(com)
+        .chain(map.orbits().map(|(center, name)| Body {
+            name,
+            center: Some(center),
+            depth: map.depth(name).unwrap(),
+        }))
+        .collect();
+    serde_json::to_string_pretty(&bodies).unwrap()
+}
+
+#[test]
+fn test_export() {
+    let orbits = vec![("COM", "B"), ("B", "C"), ("COM", "D")];
+    let map = OrbitMap::new(orbits.into_iter().map(|(center, satellite)| crate::Orbit {
+        center: center.to_string(),
+        satellite: satellite.to_string(),
+    }))
+    .unwrap();
+
+    assert_eq!(
+        to_dot(&map).unwrap(),
+        "digraph orbits {\n    \"COM\" -> \"B\";\n    \"COM\" -> \"D\";\n    \"B\" -> \"C\";\n}\n"
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&to_json(&map)).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 4);
+    assert_eq!(
+        json[3],
+        serde_json::json!({"name": "C", "center": "B", "depth": 2})
+    );
+    assert_eq!(json[0]["center"], serde_json::Value::Null);
+
+    assert_eq!(quote("B").unwrap(), "\"B\"");
+    assert_eq!(quote("say \"hi\"").unwrap(), "\"say \\\"hi\\\"\"");
+    // Backslashes go through as they are, even before an escaped quote.
+    assert_eq!(quote("a\\b").unwrap(), "\"a\\b\"");
+    assert_eq!(quote("a\\\"").unwrap(), "\"a\\\\\"\"");
+    assert_eq!(quote("é\tx").unwrap(), "\"é\tx\"");
+    assert!(quote("a\\").is_err());
+
+    let orbit = |satellite: &str| {
+        OrbitMap::new(std::iter::once(crate::Orbit {
+            center: "COM".to_string(),
+            satellite: satellite.to_string(),
+        }))
+        .unwrap()
+    };
+    assert_eq!(
+        to_dot(&orbit("Q\"1")).unwrap(),
+        "digraph orbits {\n    \"COM\" -> \"Q\\\"1\";\n}\n"
+    );
+    assert_eq!(
+        to_dot(&orbit("Q\\")),
+        Err(OrbitError::Unquotable("Q\\".to_string()))
+    );
+}
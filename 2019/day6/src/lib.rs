@@ -0,0 +1,355 @@
+use aoc::graph::{Graph, NodeId, Tree};
+use aoc::{ParseError, Solution};
+use std::fmt;
+
+pub mod export;
+mod query;
+
+pub use query::Query;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = OrbitMap;
+
+    fn parse(&self, input: &str) -> aoc::Result<OrbitMap> {
+        let orbits = aoc::parse::records(input, parse_orbit)?;
+        Ok(OrbitMap::new(orbits)?)
+    }
+
+    fn part1(&self, map: &OrbitMap) -> aoc::Result<aoc::Answer> {
+        Ok(count_orbits(map).into())
+    }
+
+    fn part2(&self, map: &OrbitMap) -> aoc::Result<aoc::Answer> {
+        Ok(calculate_orbital_transfers(map)?.into())
+    }
+}
+
+/// `satellite` orbits `center`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orbit {
+    pub center: String,
+    pub satellite: String,
+}
+
+fn parse_orbit(input: &str) -> aoc::Result<Orbit> {
+    let mut bodies = input.split(')');
+    match (bodies.next(), bodies.next(), bodies.next()) {
+        (Some(center), Some(satellite), None) if !center.is_empty() && !satellite.is_empty() => {
+            Ok(Orbit {
+                center: center.to_string(),
+                satellite: satellite.to_string(),
+            })
+        }
+        _ => Err(ParseError::at(input, 0, "expected an orbit like A)B").into()),
+    }
+}
+
+/// Why a set of orbits does not form a single tree around `COM`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrbitError {
+    /// A body the puzzle needs is not in the map.
+    MissingBody(String),
+    /// A body orbits two others at once.
+    MultipleParents {
+        body: String,
+        centers: (String, String),
+    },
+    /// A body indirectly orbits itself.
+    Cycle(String),
+    /// A body that neither is nor orbits `COM`, directly or indirectly.
+    Detached(String),
+    /// A transfer was asked for from a body that orbits nothing, which can only be `COM`.
+    OrbitsNothing(String),
+    /// A body whose name ends in a backslash, which a DOT quoted ID has no way to hold.
+    Unquotable(String),
+}
+
+impl fmt::Display for OrbitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OrbitError::MissingBody(body) => write!(f, "no body named {}", body),
+            OrbitError::MultipleParents { body, centers } => {
+                write!(f, "{} orbits both {} and {}", body, centers.0, centers.1)
+            }
+            OrbitError::Cycle(body) => write!(f, "{} is part of an orbit cycle", body),
+            OrbitError::Detached(body) => write!(f, "{} does not orbit COM", body),
+            OrbitError::OrbitsNothing(body) => write!(f, "{} does not orbit anything", body),
+            OrbitError::Unquotable(body) => {
+                write!(
+                    f,
+                    "{} ends in a backslash, so can't be written as DOT",
+                    body
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrbitError {}
+
+impl From<OrbitError> for aoc::Error {
+    fn from(err: OrbitError) -> aoc::Error {
+        aoc::Error::Puzzle(Box::new(err))
+    }
+}
+
+/// Bodies and their orbits, checked to form a tree with `COM` at the root.
+#[derive(Debug, Clone)]
+pub struct OrbitMap {
+    graph: Graph<String>,
+    tree: Tree,
+}
+
+impl OrbitMap {
+    pub fn new<I: IntoIterator<Item = Orbit>>(orbits: I) -> Result<OrbitMap, OrbitError> {
+        let mut graph = Graph::new();
+        for orbit in orbits {
+            let (center, satellite) = graph.add_edge(orbit.center, orbit.satellite, 1);
+            if let Some(other) = graph.predecessors(satellite).find(|&c| c != center) {
+                return Err(OrbitError::MultipleParents {
+                    body: graph.node(satellite).clone(),
+                    centers: (graph.node(other).clone(), graph.node(center).clone()),
+                });
+            }
+        }
+
+        graph
+            .topological_sort()
+            .map_err(|body| OrbitError::Cycle(graph.node(body).clone()))?;
+
+        let com = body(&graph, "COM")?;
+        let tree = Tree::new(&graph, com);
+        if let Some(detached) = graph.ids().find(|&id| !tree.contains(id)) {
+            return Err(OrbitError::Detached(graph.node(detached).clone()));
+        }
+
+        Ok(OrbitMap { graph, tree })
+    }
+
+    pub fn len(&self) -> usize {
+        self.graph.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.graph.is_empty()
+    }
+
+    fn id(&self, name: &str) -> Result<NodeId, OrbitError> {
+        body(&self.graph, name)
+    }
+
+    fn name(&self, id: NodeId) -> &str {
+        self.graph.node(id)
+    }
+
+    /// How many bodies `body` orbits, directly or indirectly.
+    pub fn depth(&self, body: &str) -> Result<usize, OrbitError> {
+        // Validation put every body in the tree.
+        Ok(self.tree.depth(self.id(body)?).unwrap())
+    }
+
+    /// The body `body` orbits directly, if any.
+    pub fn center(&self, body: &str) -> Result<Option<&str>, OrbitError> {
+        Ok(self.tree.parent(self.id(body)?).map(|id| self.name(id)))
+    }
+
+    /// The number of orbits between two bodies.
+    pub fn distance(&self, a: &str, b: &str) -> Result<usize, OrbitError> {
+        Ok(self.tree.distance(self.id(a)?, self.id(b)?).unwrap())
+    }
+
+    /// The number of orbital transfers to move from the body `from` orbits to the one `to`
+    /// orbits.
+    pub fn transfers(&self, from: &str, to: &str) -> Result<usize, OrbitError> {
+        let orbited = |name: &str| {
+            self.tree
+                .parent(self.id(name)?)
+                .ok_or_else(|| OrbitError::OrbitsNothing(name.to_string()))
+        };
+        Ok(self.tree.distance(orbited(from)?, orbited(to)?).unwrap())
+    }
+
+    /// The number of bodies in the subtree rooted at `body`: itself and everything orbiting it,
+    /// directly or indirectly.
+    pub fn subtree_size(&self, body: &str) -> Result<usize, OrbitError> {
+        Ok(self.graph.bfs(self.id(body)?).count())
+    }
+
+    /// The bodies that both `a` and `b` orbit, directly or indirectly, nearest first.
+    pub fn common_ancestors(&self, a: &str, b: &str) -> Result<Vec<&str>, OrbitError> {
+        let (a, b) = (self.id(a)?, self.id(b)?);
+        let lca = self.tree.lowest_common_ancestor(a, b).unwrap();
+        let ancestors = self.tree.ancestors(lca).map(|id| self.name(id));
+        if lca == a || lca == b {
+            Ok(ancestors.collect())
+        } else {
+            Ok(std::iter::once(self.name(lca)).chain(ancestors).collect())
+        }
+    }
+
+    /// Every body with the one it orbits, outwards from `COM`, which is not included.
+    pub fn orbits(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.graph.bfs(self.tree.root()).filter_map(move |(id, _)| {
+            let center = self.tree.parent(id)?;
+            Some((self.name(center), self.name(id)))
+        })
+    }
+}
+
+/// The total number of direct and indirect orbits: the sum of every body's depth below `COM`.
+pub fn count_orbits(map: &OrbitMap) -> usize {
+    map.graph.ids().filter_map(|id| map.tree.depth(id)).sum()
+}
+
+/// The number of orbital transfers to move from the body `YOU` orbits to the one `SAN` orbits.
+pub fn calculate_orbital_transfers(map: &OrbitMap) -> Result<usize, OrbitError> {
+    map.transfers("YOU", "SAN")
+}
+
+fn body(graph: &Graph<String>, name: &str) -> Result<NodeId, OrbitError> {
+    graph
+        .id(&name.to_string())
+        .ok_or_else(|| OrbitError::MissingBody(name.to_string()))
+}
+
+#[cfg(test)]
+fn orbit(center: &str, satellite: &str) -> Orbit {
+    Orbit {
+        center: center.to_string(),
+        satellite: satellite.to_string(),
+    }
+}
+
+#[cfg(test)]
+fn map_of(input: &str) -> Result<OrbitMap, OrbitError> {
+    OrbitMap::new(
+        input
+            .split_whitespace()
+            .map(|line| parse_orbit(line).unwrap()),
+    )
+}
+
+#[test]
+fn test_examples() {
+    let map = Day6
+        .parse("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\n")
+        .unwrap();
+    assert_eq!(count_orbits(&map), 42);
+    assert_eq!(
+        calculate_orbital_transfers(&map),
+        Err(OrbitError::MissingBody("YOU".to_string()))
+    );
+
+    assert_eq!(calculate_orbital_transfers(&example()), Ok(4));
+}
+
+#[test]
+fn test_parse_orbit() {
+    assert_eq!(parse_orbit("A)B").unwrap(), orbit("A", "B"));
+    for bad in &["AB", "A)", ")B", "A)B)C"] {
+        assert!(parse_orbit(bad).is_err(), "{:?} parsed", bad);
+    }
+
+    match Day6.parse("COM)A\nA-B\n") {
+        Err(aoc::Error::Parse(err)) => assert_eq!((err.line(), err.column()), (2, 1)),
+        other => panic!("expected a parse error, got {:?}", other.map(|m| m.len())),
+    }
+}
+
+#[cfg(test)]
+fn example() -> OrbitMap {
+    map_of("COM)B B)C C)D D)E E)F B)G G)H D)I E)J J)K K)L K)YOU I)SAN").unwrap()
+}
+
+#[test]
+fn test_queries() {
+    let map = example();
+    assert_eq!(map.depth("COM"), Ok(0));
+    assert_eq!(map.depth("L"), Ok(7));
+    assert_eq!(map.center("YOU"), Ok(Some("K")));
+    assert_eq!(map.center("COM"), Ok(None));
+    assert_eq!(map.distance("YOU", "SAN"), Ok(6));
+    assert_eq!(map.distance("D", "D"), Ok(0));
+    assert_eq!(map.transfers("YOU", "SAN"), Ok(4));
+    assert_eq!(
+        map.transfers("COM", "SAN"),
+        Err(OrbitError::OrbitsNothing("COM".to_string()))
+    );
+    assert_eq!(map.subtree_size("COM"), Ok(map.len()));
+    assert_eq!(map.subtree_size("E"), Ok(6));
+    assert_eq!(map.subtree_size("YOU"), Ok(1));
+    assert_eq!(
+        map.common_ancestors("YOU", "SAN"),
+        Ok(vec!["D", "C", "B", "COM"])
+    );
+    assert_eq!(
+        map.common_ancestors("E", "L"),
+        Ok(vec!["D", "C", "B", "COM"])
+    );
+    assert_eq!(map.common_ancestors("COM", "L"), Ok(vec![]));
+    assert_eq!(
+        map.depth("X"),
+        Err(OrbitError::MissingBody("X".to_string()))
+    );
+    assert_eq!(map.orbits().count(), map.len() - 1);
+    assert_eq!(map.orbits().next(), Some(("COM", "B")));
+}
+
+#[test]
+fn test_validation() {
+    assert_eq!(
+        map_of("A)B B)C").unwrap_err(),
+        OrbitError::MissingBody("COM".to_string())
+    );
+    assert_eq!(
+        map_of("COM)A COM)B A)C B)C").unwrap_err(),
+        OrbitError::MultipleParents {
+            body: "C".to_string(),
+            centers: ("A".to_string(), "B".to_string()),
+        }
+    );
+    assert!(matches!(
+        map_of("COM)A X)Y Y)Z Z)X").unwrap_err(),
+        OrbitError::Cycle(_)
+    ));
+    assert_eq!(
+        map_of("COM)A X)Y").unwrap_err(),
+        OrbitError::Detached("X".to_string())
+    );
+    assert_eq!(
+        map_of("X)COM COM)A").unwrap_err(),
+        OrbitError::Detached("X".to_string())
+    );
+    // A duplicated orbit is harmless.
+    assert_eq!(count_orbits(&map_of("COM)A COM)A").unwrap()), 1);
+
+    let err = aoc::Error::from(map_of("COM)A A)COM").unwrap_err());
+    assert_eq!(err.to_string(), "COM is part of an orbit cycle");
+}
+
+#[test]
+fn test_million_body_chain() {
+    const BODIES: usize = 1_000_000;
+
+    let name = |i: usize| match i {
+        0 => "COM".to_string(),
+        _ => format!("B{}", i),
+    };
+    let chain = (1..BODIES).map(|i| Orbit {
+        center: name(i - 1),
+        satellite: name(i),
+    });
+    let branches = vec![orbit("B10", "SAN"), orbit(&name(BODIES - 1), "YOU")];
+    let map = OrbitMap::new(chain.chain(branches)).unwrap();
+
+    assert_eq!(count_orbits(&map), BODIES * (BODIES - 1) / 2 + 11 + BODIES);
+    assert_eq!(calculate_orbital_transfers(&map), Ok(BODIES - 1 - 10));
+
+    let cycle = (1..BODIES).map(|i| orbit(&name(i), &name((i % (BODIES - 1)) + 1)));
+    assert!(matches!(
+        OrbitMap::new(cycle.chain(vec![orbit("COM", "X")])).unwrap_err(),
+        OrbitError::Cycle(_)
+    ));
+}
@@ -0,0 +1,89 @@
+//! Questions about an orbit map, as asked on the command line.
+
+use crate::{export, OrbitMap};
+use aoc::Error;
+
+pub const USAGE: &str = "usage: day6 [QUERY...]\n\
+    queries:\n  \
+      depth BODY         how many bodies BODY orbits\n  \
+      distance A B       orbits between A and B\n  \
+      transfers A B      transfers between the bodies A and B orbit\n  \
+      subtree BODY       how many bodies are BODY or orbit it\n  \
+      ancestors A B      bodies both A and B orbit, nearest first\n  \
+      dot                the map as a Graphviz digraph\n  \
+      json               the map as JSON";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    Depth(String),
+    Distance(String, String),
+    Transfers(String, String),
+    Subtree(String),
+    Ancestors(String, String),
+    Dot,
+    Json,
+}
+
+impl Query {
+    /// Reads queries one after another from command line arguments.
+    pub fn parse_all(args: &[String]) -> aoc::Result<Vec<Query>> {
+        let usage = || Error::Usage(USAGE.to_string());
+        let mut queries = Vec::new();
+        let mut args = args.iter().cloned();
+        while let Some(name) = args.next() {
+            let mut body = || args.next().ok_or_else(usage);
+            let query = match name.as_str() {
+                "depth" => Query::Depth(body()?),
+                "distance" => Query::Distance(body()?, body()?),
+                "transfers" => Query::Transfers(body()?, body()?),
+                "subtree" => Query::Subtree(body()?),
+                "ancestors" => Query::Ancestors(body()?, body()?),
+                "dot" => Query::Dot,
+                "json" => Query::Json,
+                _ => return Err(usage()),
+            };
+            queries.push(query);
+        }
+        Ok(queries)
+    }
+
+    pub fn answer(&self, map: &OrbitMap) -> aoc::Result<String> {
+        let answer = match self {
+            Query::Depth(body) => map.depth(body)?.to_string(),
+            Query::Distance(a, b) => map.distance(a, b)?.to_string(),
+            Query::Transfers(a, b) => map.transfers(a, b)?.to_string(),
+            Query::Subtree(body) => map.subtree_size(body)?.to_string(),
+            Query::Ancestors(a, b) => map.common_ancestors(a, b)?.join(" "),
+            Query::Dot => export::to_dot(map)?,
+            Query::Json => export::to_json(map),
+        };
+        Ok(answer)
+    }
+}
+
+#[test]
+fn test_queries() {
+    let args: Vec<String> = "depth L ancestors YOU SAN dot"
+        .split(' ')
+        .map(String::from)
+        .collect();
+    let queries = Query::parse_all(&args).unwrap();
+    assert_eq!(
+        queries,
+        vec![
+            Query::Depth("L".to_string()),
+            Query::Ancestors("YOU".to_string(), "SAN".to_string()),
+            Query::Dot,
+        ]
+    );
+
+    let map = crate::example();
+    assert_eq!(queries[0].answer(&map).unwrap(), "7");
+    assert_eq!(queries[1].answer(&map).unwrap(), "D C B COM");
+    assert!(Query::Subtree("X".to_string()).answer(&map).is_err());
+
+    for bad in &[vec!["depth"], vec!["transfers", "YOU"], vec!["size", "B"]] {
+        let args: Vec<String> = bad.iter().map(|s| s.to_string()).collect();
+        assert!(Query::parse_all(&args).is_err(), "{:?} parsed", bad);
+    }
+}
@@ -1,60 +1,104 @@
-use crate::computer;
+use aoc::intcode::{self, recording::Recorder, Budget, State};
 use crossbeam::crossbeam_channel::{unbounded, Receiver};
-use std::thread;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// Generous upper bounds on the work one amplifier may do for a phase setting. The timeout also
+// catches amplifiers that all wait on each other for input.
+const MAX_INSTRUCTIONS: u64 = 1_000_000;
+const TIMEOUT: Duration = Duration::from_secs(5);
 
 pub struct AmplifierGroup {
-    initial_state: Vec<i32>,
+    initial_state: Vec<i64>,
+    recorder: Option<Recorder>,
+    budget: Budget,
 }
 
 impl AmplifierGroup {
-    pub fn new(initial_state: &Vec<i32>) -> AmplifierGroup {
+    pub fn new(initial_state: &[i64]) -> AmplifierGroup {
         AmplifierGroup {
-            initial_state: initial_state.clone(),
+            initial_state: initial_state.to_vec(),
+            recorder: None,
+            budget: Budget::instructions(MAX_INSTRUCTIONS).with_timeout(TIMEOUT),
         }
     }
 
-    pub fn execute(&mut self, phases: &Vec<i32>) -> i32 {
+    /// Logs the I/O of every amplifier in subsequent executions, using its position in the
+    /// chain as the machine ID.
+    pub fn record(&mut self, recorder: Recorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Replaces the limits on the work each amplifier may do in one execution.
+    pub fn set_budget(&mut self, budget: Budget) {
+        self.budget = budget;
+    }
+
+    /// The last signal out of the chain, once every amplifier has halted.
+    pub fn execute(&mut self, phases: &[i64]) -> aoc::Result<i64> {
         let (input_tx0, input_rx0) = unbounded();
 
-        let output_rx0 = self.mk_computer(vec![phases[0], 0], input_rx0);
-        let output_rx1 = self.mk_computer(vec![phases[1]], output_rx0);
-        let output_rx2 = self.mk_computer(vec![phases[2]], output_rx1);
-        let output_rx3 = self.mk_computer(vec![phases[3]], output_rx2);
-        let output_rx4 = self.mk_computer(vec![phases[4]], output_rx3);
+        let mut amplifiers = Vec::new();
+        let mut input_rx = input_rx0;
+        for (machine, &phase) in phases.iter().enumerate() {
+            let mut initial_inputs = vec![phase];
+            if machine == 0 {
+                initial_inputs.push(0);
+            }
+            let (output_rx, amplifier) = self.mk_computer(machine, initial_inputs, input_rx);
+            amplifiers.push(amplifier);
+            input_rx = output_rx;
+        }
+
+        let mut signal = None;
+        for s in input_rx {
+            signal = Some(s);
+            // Fails once the first amplifier has stopped, which the join below reports.
+            let _ = input_tx0.send(s);
+        }
+        drop(input_tx0);
 
-        let mut signal = 0;
-        for s in output_rx4 {
-            signal = s;
-            input_tx0.send(s).unwrap();
+        for (machine, (computer, forwarder)) in amplifiers.into_iter().enumerate() {
+            let state = computer.join().expect("amplifier thread panicked");
+            forwarder.join().expect("forwarding thread panicked");
+            if let State::Halted(_) = state {
+                continue;
+            }
+            return Err(aoc::Error::ExecError(format!(
+                "amplifier {} stopped with {:?}",
+                machine, state
+            )));
         }
 
-        signal
+        signal.ok_or_else(|| aoc::Error::ExecError("the amplifiers sent no signal".to_string()))
     }
 
-    fn mk_computer(&self, initial_inputs: Vec<i32>, input_rx: Receiver<i32>) -> Receiver<i32> {
+    /// Starts an amplifier reading `initial_inputs` and then everything from `input_rx`,
+    /// returning its output and the threads running it and feeding it.
+    fn mk_computer(
+        &self,
+        machine: usize,
+        initial_inputs: Vec<i64>,
+        input_rx: Receiver<i64>,
+    ) -> (Receiver<i64>, (JoinHandle<State>, JoinHandle<()>)) {
         let (tx, rx) = unbounded();
 
-        let (mut comp, output_rx) = computer::new(&self.initial_state, rx.clone());
-        thread::spawn(move || {
-            comp.run();
-        });
-
-        thread::spawn(move || {
-            for inp in initial_inputs.iter() {
-                tx.send(*inp).unwrap();
-            }
-
-            for signal in input_rx {
-                match tx.send(signal) {
-                    Ok(_) => (),
-                    Err(err) => println!("Error sending {:?}", err.into_inner()),
-                }
+        let (mut comp, output_rx) = intcode::new(&self.initial_state, rx);
+        if let Some(recorder) = &self.recorder {
+            comp.record(machine, recorder.clone());
+        }
+        let budget = self.budget;
+        let computer = thread::spawn(move || comp.run_with_budget(budget));
+
+        let forwarder = thread::spawn(move || {
+            // Sending fails once the amplifier has stopped, which the caller reports. Keep
+            // draining the input anyway, so that the amplifier before this one can still write.
+            for inp in initial_inputs.into_iter().chain(input_rx) {
+                let _ = tx.send(inp);
             }
-            drop(tx);
-            drop(rx);
         });
 
-        output_rx
+        (output_rx, (computer, forwarder))
     }
 }
 
@@ -65,7 +109,7 @@ fn test_amplifier_group1() {
     ];
     let phases = vec![4, 3, 2, 1, 0];
     let mut amps = AmplifierGroup::new(&initial_state);
-    let output = amps.execute(&phases);
+    let output = amps.execute(&phases).unwrap();
 
     assert_eq!(output, 43210);
 }
@@ -78,7 +122,7 @@ fn test_amplifier_group2() {
     ];
     let phases = vec![0, 1, 2, 3, 4];
     let mut amps = AmplifierGroup::new(&initial_state);
-    let output = amps.execute(&phases);
+    let output = amps.execute(&phases).unwrap();
 
     assert_eq!(output, 54321);
 }
@@ -91,7 +135,7 @@ fn test_amplifier_group3() {
     ];
     let phases = vec![1, 0, 4, 3, 2];
     let mut amps = AmplifierGroup::new(&initial_state);
-    let output = amps.execute(&phases);
+    let output = amps.execute(&phases).unwrap();
 
     assert_eq!(output, 65210);
 }
@@ -104,7 +148,7 @@ fn test_feedback_amplifier_group1() {
     ];
     let phases = vec![9, 8, 7, 6, 5];
     let mut amps = AmplifierGroup::new(&initial_state);
-    let output = amps.execute(&phases);
+    let output = amps.execute(&phases).unwrap();
 
     assert_eq!(output, 139629729);
 }
@@ -118,7 +162,56 @@ fn test_feedback_amplifier_group2() {
     ];
     let phases = vec![9, 7, 8, 5, 6];
     let mut amps = AmplifierGroup::new(&initial_state);
-    let output = amps.execute(&phases);
+    let output = amps.execute(&phases).unwrap();
 
     assert_eq!(output, 18216);
 }
+
+#[test]
+fn test_record_and_replay_feedback_session() {
+    use aoc::intcode::recording::{replay, Direction, Log};
+    use aoc::intcode::Budget;
+
+    let initial_state = vec![
+        3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1, 28,
+        1005, 28, 6, 99, 0, 0, 5,
+    ];
+    let phases = vec![9, 8, 7, 6, 5];
+    let recorder = Recorder::new();
+    let mut amps = AmplifierGroup::new(&initial_state);
+    amps.record(recorder.clone());
+    let output = amps.execute(&phases).unwrap();
+
+    let log: Log = recorder.log().to_string().parse().unwrap();
+    assert_eq!(log.values(4, Direction::Output).last(), Some(&output));
+    assert_eq!(log.values(0, Direction::Input)[..2], [9, 0]);
+    assert!(replay(&initial_state, &log, Budget::instructions(10_000)).is_ok());
+
+    let mut changed_state = initial_state.clone();
+    changed_state[10] = 3;
+    assert!(replay(&changed_state, &log, Budget::instructions(10_000)).is_err());
+}
+
+#[test]
+fn test_stuck_amplifiers() {
+    // Loops forever.
+    let mut amps = AmplifierGroup::new(&[1105, 1, 0]);
+    amps.set_budget(Budget::instructions(1000));
+    assert!(amps.execute(&[0, 1, 2, 3, 4]).is_err());
+
+    // Reads its phase, then waits for a second input that never comes.
+    let mut amps = AmplifierGroup::new(&[3, 9, 3, 9, 3, 9, 4, 9, 99, 0]);
+    amps.set_budget(Budget::timeout(Duration::from_millis(100)));
+    assert!(amps.execute(&[0, 1, 2, 3, 4]).is_err());
+
+    // Bad opcode after the first output.
+    let mut amps = AmplifierGroup::new(&[3, 9, 3, 9, 4, 9, 42, 0, 0, 0]);
+    assert!(amps.execute(&[0, 1, 2, 3, 4]).is_err());
+
+    // The second amplifier fails at once, while the first keeps writing to it.
+    let program = [
+        3, 20, 1008, 20, 1, 21, 1005, 21, 19, 4, 20, 4, 20, 4, 20, 99, 0, 0, 0, 42, 0, 0,
+    ];
+    let mut amps = AmplifierGroup::new(&program);
+    assert!(amps.execute(&[0, 1, 2, 3, 4]).is_err());
+}
@@ -0,0 +1,125 @@
+//! Decoded images as files and as text.
+//!
+//! Puzzle images are tiny, so the file formats take a `scale` that blows each pixel up into a
+//! square that many pixels across. Transparent pixels are transparent in PNG, white in PBM
+//! (which only has black and white) and grey in PPM.
+
+use crate::image::Pixel;
+use aoc::grid::{Grid, Point};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+/// Each pixel as a `scale` by `scale` square.
+fn scaled(image: &Grid<Pixel>, scale: usize) -> Grid<Pixel> {
+    let mut big = Grid::new(
+        image.width() * scale,
+        image.height() * scale,
+        Pixel::Transparent,
+    );
+    let s = scale as i64;
+    for (p, pixel) in big.iter_mut() {
+        *pixel = *image.get(Point::new(p.x / s, p.y / s)).unwrap();
+    }
+    big
+}
+
+/// A plain (`P1`) portable bitmap.
+pub fn pbm(image: &Grid<Pixel>, scale: usize) -> String {
+    let image = scaled(image, scale);
+    let mut pbm = format!("P1\n{} {}\n", image.width(), image.height());
+    for row in image.rows() {
+        let bits: Vec<&str> = row
+            .iter()
+            .map(|pixel| if *pixel == Pixel::Black { "1" } else { "0" })
+            .collect();
+        pbm.push_str(&bits.join(" "));
+        pbm.push('\n');
+    }
+    pbm
+}
+
+/// A plain (`P3`) portable pixmap.
+pub fn ppm(image: &Grid<Pixel>, scale: usize) -> String {
+    let image = scaled(image, scale);
+    let mut ppm = format!("P3\n{} {}\n255\n", image.width(), image.height());
+    for row in image.rows() {
+        let colours: Vec<String> = row
+            .iter()
+            .map(|&pixel| {
+                let [r, g, b, _] = rgba(pixel);
+                format!("{} {} {}", r, g, b)
+            })
+            .collect();
+        writeln!(ppm, "{}", colours.join("  ")).unwrap();
+    }
+    ppm
+}
+
+fn rgba(pixel: Pixel) -> [u8; 4] {
+    match pixel {
+        Pixel::Black => [0, 0, 0, 255],
+        Pixel::White => [255, 255, 255, 255],
+        Pixel::Transparent => [128, 128, 128, 0],
+    }
+}
+
+/// Writes an RGBA PNG.
+pub fn png<W: Write>(image: &Grid<Pixel>, scale: usize, out: W) -> io::Result<()> {
+    let image = scaled(image, scale);
+    let mut encoder = png::Encoder::new(out, image.width() as u32, image.height() as u32);
+    encoder.set_color(png::ColorType::RGBA);
+    encoder.set_depth(png::BitDepth::Eight);
+    let data: Vec<u8> = image.iter().flat_map(|(_, &pixel)| rgba(pixel)).collect();
+    encoder.write_header()?.write_image_data(&data)?;
+    Ok(())
+}
+
+/// Each pixel as two spaces on a black or white background, with transparent ones left as the
+/// terminal's own background.
+pub fn ansi(image: &Grid<Pixel>) -> String {
+    let mut out = String::new();
+    for row in image.rows() {
+        for pixel in row {
+            match pixel {
+                Pixel::Black => out.push_str("\x1b[40m  "),
+                Pixel::White => out.push_str("\x1b[107m  "),
+                Pixel::Transparent => out.push_str("\x1b[0m  "),
+            }
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+#[test]
+fn test_export() {
+    use crate::image::Image;
+
+    let image = Image::parse("0222112222120000", 2, 2)
+        .unwrap()
+        .composite()
+        .unwrap();
+    assert_eq!(pbm(&image, 1), "P1\n2 2\n1 0\n0 1\n");
+    assert_eq!(
+        pbm(&image, 2),
+        "P1\n4 4\n1 1 0 0\n1 1 0 0\n0 0 1 1\n0 0 1 1\n"
+    );
+    assert_eq!(
+        ppm(&image, 1),
+        "P3\n2 2\n255\n0 0 0  255 255 255\n255 255 255  0 0 0\n"
+    );
+    assert_eq!(
+        ansi(&image),
+        "\x1b[40m  \x1b[107m  \x1b[0m\n\x1b[107m  \x1b[40m  \x1b[0m\n"
+    );
+
+    let mut file = Vec::new();
+    png(&image, 3, &mut file).unwrap();
+    assert!(file.starts_with(b"\x89PNG\r\n\x1a\n"));
+    let (info, mut reader) = png::Decoder::new(&file[..]).read_info().unwrap();
+    assert_eq!((info.width, info.height), (6, 6));
+    let mut pixels = vec![0; info.buffer_size()];
+    reader.next_frame(&mut pixels).unwrap();
+    assert_eq!(&pixels[..4], &[0, 0, 0, 255]);
+    assert_eq!(&pixels[12..16], &[255, 255, 255, 255]);
+}
@@ -0,0 +1,273 @@
+//! Images in the Space Image Format: layers of digits, each `width * height` long, stacked so
+//! that the first visible pixel at a position shows through.
+
+use aoc::grid::Grid;
+use aoc::ParseError;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pixel {
+    Black,
+    White,
+    Transparent,
+}
+
+impl Pixel {
+    pub fn from_digit(digit: u8) -> Option<Pixel> {
+        match digit {
+            0 => Some(Pixel::Black),
+            1 => Some(Pixel::White),
+            2 => Some(Pixel::Transparent),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageError {
+    /// A width or height of zero.
+    NoSize,
+    /// The last layer stops short of `expected` pixels.
+    Truncated {
+        layer: usize,
+        pixels: usize,
+        expected: usize,
+    },
+    /// No layers at all.
+    Empty,
+    /// A layer this size would be bigger than the whole image.
+    TooLarge { width: usize, height: usize },
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageError::NoSize => f.write_str("images need a width and height of at least 1"),
+            ImageError::Truncated {
+                layer,
+                pixels,
+                expected,
+            } => write!(
+                f,
+                "layer {} has {} of its {} pixels",
+                layer, pixels, expected
+            ),
+            ImageError::Empty => f.write_str("the image has no layers"),
+            ImageError::TooLarge { width, height } => write!(
+                f,
+                "a {}x{} layer is bigger than the whole image",
+                width, height
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<ImageError> for aoc::Error {
+    fn from(err: ImageError) -> aoc::Error {
+        aoc::Error::Puzzle(Box::new(err))
+    }
+}
+
+/// The pixels of every layer, one after another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Pixel>,
+}
+
+impl Image {
+    /// An image of layers `width` by `height`, which must fit in `pixels` at least once.
+    pub fn new(width: usize, height: usize, pixels: Vec<Pixel>) -> Result<Image, ImageError> {
+        if width == 0 || height == 0 {
+            return Err(ImageError::NoSize);
+        }
+        if pixels.is_empty() {
+            return Err(ImageError::Empty);
+        }
+        if width
+            .checked_mul(height)
+            .is_none_or(|size| size > pixels.len())
+        {
+            return Err(ImageError::TooLarge { width, height });
+        }
+        Ok(Image {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Reads the digits of an image `width` pixels wide and `height` high. Only `0`, `1` and `2`
+    /// are pixels; surrounding whitespace is ignored.
+    pub fn parse(input: &str, width: usize, height: usize) -> aoc::Result<Image> {
+        let trimmed = input.trim();
+        let pixels = trimmed
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10)
+                    .and_then(|d| Pixel::from_digit(d as u8))
+                    .ok_or_else(|| {
+                        let msg = format!("expected 0, 1 or 2, found {:?}", c);
+                        aoc::Error::from(ParseError::at(trimmed, i, msg)).locate(input, trimmed)
+                    })
+            })
+            .collect::<aoc::Result<_>>()?;
+        Ok(Image::new(width, height, pixels)?)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The layers from the top down. A short last layer comes out as `ImageError::Truncated`.
+    pub fn layers(&self) -> Layers<'_> {
+        Layers {
+            image: self,
+            index: 0,
+        }
+    }
+
+    /// The layers stacked up, with pixels that every layer leaves transparent staying so.
+    pub fn composite(&self) -> Result<Grid<Pixel>, ImageError> {
+        let mut image = Grid::new(self.width, self.height, Pixel::Transparent);
+        let mut empty = true;
+        for layer in self.layers() {
+            let layer = layer?;
+            for ((_, pixel), &above) in image.iter_mut().zip(layer.pixels()) {
+                if *pixel == Pixel::Transparent {
+                    *pixel = above;
+                }
+            }
+            empty = false;
+        }
+        if empty {
+            return Err(ImageError::Empty);
+        }
+        Ok(image)
+    }
+}
+
+/// One complete layer of an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layer<'a> {
+    width: usize,
+    pixels: &'a [Pixel],
+}
+
+impl<'a> Layer<'a> {
+    pub fn pixels(&self) -> &'a [Pixel] {
+        self.pixels
+    }
+
+    /// How many pixels are `pixel`.
+    pub fn count(&self, pixel: Pixel) -> usize {
+        self.pixels.iter().filter(|&&p| p == pixel).count()
+    }
+
+    pub fn to_grid(&self) -> Grid<Pixel> {
+        Grid::from_cells(self.width, self.pixels.to_vec())
+    }
+}
+
+pub struct Layers<'a> {
+    image: &'a Image,
+    index: usize,
+}
+
+impl<'a> Iterator for Layers<'a> {
+    type Item = Result<Layer<'a>, ImageError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let size = self.image.width * self.image.height;
+        let start = self.index * size;
+        if start >= self.image.pixels.len() {
+            return None;
+        }
+
+        let pixels = &self.image.pixels[start..(start + size).min(self.image.pixels.len())];
+        let layer = self.index;
+        self.index += 1;
+        if pixels.len() < size {
+            return Some(Err(ImageError::Truncated {
+                layer,
+                pixels: pixels.len(),
+                expected: size,
+            }));
+        }
+        Some(Ok(Layer {
+            width: self.image.width,
+            pixels,
+        }))
+    }
+}
+
+#[test]
+fn test_image() {
+    use aoc::grid::Point;
+    use Pixel::*;
+
+    let image = Image::parse("0222112222120000\n", 2, 2).unwrap();
+    let layers: Vec<_> = image.layers().map(Result::unwrap).collect();
+    assert_eq!(layers.len(), 4);
+    assert_eq!(
+        layers[1].pixels(),
+        &[White, White, Transparent, Transparent]
+    );
+    assert_eq!(layers[3].count(Black), 4);
+    assert_eq!(
+        layers[0].to_grid().get(Point::new(1, 0)),
+        Some(&Transparent)
+    );
+    assert_eq!(
+        image
+            .composite()
+            .unwrap()
+            .iter()
+            .map(|(_, &p)| p)
+            .collect::<Vec<_>>(),
+        vec![Black, White, White, Black]
+    );
+
+    let short = Image::parse("1222 12", 2, 2);
+    assert!(short.is_err(), "a space inside the digits parsed");
+    let short = Image::parse("012210", 2, 2).unwrap();
+    let layers: Vec<_> = short.layers().collect();
+    assert_eq!(
+        layers[1],
+        Err(ImageError::Truncated {
+            layer: 1,
+            pixels: 2,
+            expected: 4
+        })
+    );
+    assert_eq!(layers.len(), 2);
+    assert!(short.composite().is_err());
+
+    let see_through = Image::parse("2220", 2, 1).unwrap().composite().unwrap();
+    assert_eq!(see_through.get(Point::ORIGIN), Some(&Transparent));
+    assert!(Image::parse("", 2, 1).is_err());
+    assert_eq!(Image::new(2, 1, vec![]), Err(ImageError::Empty));
+    assert_eq!(
+        Image::new(3, 2, vec![Black; 4]),
+        Err(ImageError::TooLarge {
+            width: 3,
+            height: 2
+        })
+    );
+    assert_eq!(
+        Image::new(usize::MAX, 2, vec![Black; 4]),
+        Err(ImageError::TooLarge {
+            width: usize::MAX,
+            height: 2
+        })
+    );
+    assert!(Image::parse("3", 1, 1).is_err());
+    assert_eq!(Image::new(0, 1, vec![]), Err(ImageError::NoSize));
+}
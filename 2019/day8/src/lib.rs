@@ -0,0 +1,83 @@
+use aoc::grid::Grid;
+use aoc::Solution;
+use image::{Image, ImageError, Pixel};
+
+pub mod export;
+pub mod image;
+
+pub const WIDTH: usize = 25;
+pub const HEIGHT: usize = 6;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Image;
+
+    fn parse(&self, input: &str) -> aoc::Result<Image> {
+        Image::parse(input, WIDTH, HEIGHT)
+    }
+
+    fn part1(&self, image: &Image) -> aoc::Result<aoc::Answer> {
+        Ok(checksum(image)?.into())
+    }
+
+    fn part2(&self, image: &Image) -> aoc::Result<aoc::Answer> {
+        Ok(read(&image.composite()?)?.into())
+    }
+}
+
+/// Number of ones multiplied by number of twos in the layer with the fewest zeroes.
+pub fn checksum(image: &Image) -> Result<usize, ImageError> {
+    let mut fewest: Option<(usize, usize)> = None;
+    for layer in image.layers() {
+        let layer = layer?;
+        let zeroes = layer.count(Pixel::Black);
+        if fewest.is_none_or(|(min, _)| zeroes < min) {
+            let checksum = layer.count(Pixel::White) * layer.count(Pixel::Transparent);
+            fewest = Some((zeroes, checksum));
+        }
+    }
+    fewest
+        .map(|(_, checksum)| checksum)
+        .ok_or(ImageError::Empty)
+}
+
+/// The letters a decoded image spells out.
+pub fn read(image: &Grid<Pixel>) -> aoc::Result<String> {
+    Ok(aoc::ocr::read(image, |&pixel| pixel == Pixel::White)?)
+}
+
+/// Each row on a line of its own, with white pixels as `1` and the rest as spaces.
+pub fn render(image: &Grid<Pixel>) -> String {
+    let mut s = String::new();
+
+    for row in image.rows() {
+        s.push('\n');
+        s.extend(row.iter().map(|pixel| match pixel {
+            Pixel::White => '1',
+            Pixel::Black | Pixel::Transparent => ' ',
+        }));
+    }
+
+    s
+}
+
+#[test]
+fn test_checksum() {
+    let image = Image::parse("123456789012", 3, 2);
+    assert!(image.is_err());
+
+    let image = Image::parse("112022000112", 3, 2).unwrap();
+    assert_eq!(checksum(&image), Ok(6));
+    let short = Image::parse("1120220001", 3, 2).unwrap();
+    assert!(checksum(&short).is_err());
+    assert_eq!(
+        render(
+            &Image::parse("0222112222120000", 2, 2)
+                .unwrap()
+                .composite()
+                .unwrap()
+        ),
+        "\n 1\n1 "
+    );
+}
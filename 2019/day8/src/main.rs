@@ -1,81 +1,58 @@
-use std::fs::File;
-use std::io;
-use std::io::Read;
-
-const WIDTH: usize = 25;
-const HEIGHT: usize = 6;
-const SIZE: usize = WIDTH * HEIGHT;
-
-fn main() -> io::Result<()> {
-    let decoder = Decoder::new("input")?;
-    let mut min_num_zeroes = std::i32::MAX;
-    let mut checksum = 0;
-    let mut image = [2; SIZE];
-
-    for layer in decoder {
-        let (zeroes, ones, twos) = layer
-            .iter()
-            .enumerate()
-            .fold((0, 0, 0), |acc, (idx, byte)| match byte {
-                b'0' => {
-                    if image[idx] == 2 {
-                        image[idx] = 0;
-                    }
-                    (acc.0 + 1, acc.1, acc.2)
-                }
-                b'1' => {
-                    if image[idx] == 2 {
-                        image[idx] = 1;
-                    }
-                    (acc.0, acc.1 + 1, acc.2)
-                }
-                b'2' => (acc.0, acc.1, acc.2 + 1),
-                _ => unreachable!(),
-            });
-
-        if zeroes < min_num_zeroes {
-            min_num_zeroes = zeroes;
-            checksum = ones * twos;
-        }
+use aoc::{Error, Solution};
+use day8::{export, Day8};
+use std::env;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::process;
+
+const USAGE: &str =
+    "usage: day8 [--size WIDTH HEIGHT] [--scale N] [--png PATH] [--pbm PATH] [--ppm PATH] [--show]";
+
+fn main() {
+    if let Err(err) = try_main() {
+        eprintln!("{}", err);
+        process::exit(2);
     }
+}
 
-    println!("Checksum: {}", checksum);
-
-    for i in 0..SIZE {
-        if i % WIDTH == 0 {
-            print!("\n");
-        }
-
-        match image[i] {
-            0 => print!(" "),
-            1 => print!("1"),
-            _ => unreachable!(),
+fn try_main() -> aoc::Result<()> {
+    let usage = || Error::Usage(USAGE.to_string());
+    let (mut width, mut height) = (day8::WIDTH, day8::HEIGHT);
+    let mut scale = 10;
+    let (mut png, mut pbm, mut ppm) = (None, None, None);
+    let mut show = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                width = args.next().ok_or_else(usage)?.parse()?;
+                height = args.next().ok_or_else(usage)?.parse()?;
+            }
+            "--scale" => scale = args.next().ok_or_else(usage)?.parse()?,
+            "--png" => png = Some(args.next().ok_or_else(usage)?),
+            "--pbm" => pbm = Some(args.next().ok_or_else(usage)?),
+            "--ppm" => ppm = Some(args.next().ok_or_else(usage)?),
+            "--show" => show = true,
+            _ => return Err(usage()),
         }
     }
 
-    Ok(())
-}
-
-struct Decoder {
-    data: File,
-}
+    let image = day8::image::Image::parse(&aoc::input::read(2019, 8)?, width, height)?;
+    println!("Checksum: {}", Day8.part1(&image)?);
 
-impl Decoder {
-    fn new(path: &str) -> io::Result<Decoder> {
-        let data = File::open(path)?;
-        Ok(Decoder { data })
+    let decoded = image.composite()?;
+    println!("Message: {}", day8::read(&decoded)?);
+    if let Some(path) = png {
+        export::png(&decoded, scale, BufWriter::new(File::create(path)?))?;
     }
-}
-
-impl Iterator for Decoder {
-    type Item = Vec<u8>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut buf = [0; SIZE];
-        let result = self.data.read(&mut buf[..]);
-        match result {
-            Ok(n) if n == SIZE => Some(buf[..n].to_vec()),
-            _ => None,
-        }
+    if let Some(path) = pbm {
+        fs::write(path, export::pbm(&decoded, scale))?;
+    }
+    if let Some(path) = ppm {
+        fs::write(path, export::ppm(&decoded, scale))?;
     }
+    if show {
+        print!("{}", export::ansi(&decoded));
+    }
+    Ok(())
 }
@@ -0,0 +1,58 @@
+use aoc::input::{self, Resolver};
+use aoc::intcode::{self, compiler};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[path = "src/programs.rs"]
+mod programs;
+
+/// Stands in for the compiled BOOST program when there is no input to compile.
+const STUB: &str = "/// No input was found to compile, so no program is `BOOST`.
+pub const BOOST: &[i64] = &[];
+
+/// Interprets whatever program the computer holds.
+pub fn boost(c: &mut aoc::intcode::Computer) -> aoc::intcode::State {
+    c.run_with_budget(aoc::intcode::Budget::unlimited())
+}
+";
+
+fn main() -> aoc::Result<()> {
+    println!("cargo:rerun-if-changed=src/programs.rs");
+
+    // The environment is left out: `$AOC_INPUT` names whichever day is being run, not this one.
+    let resolver = Resolver::new(input::repo_root());
+    let boost = match resolver.resolve(2019, 9) {
+        Ok(path) => {
+            println!("cargo:rerun-if-changed={}", path.display());
+            let boost = intcode::parse_program(&fs::read_to_string(&path)?)?;
+            [
+                format!(
+                    "/// The program `boost` was compiled from.\npub const BOOST: &[i64] = &{:?};\n",
+                    boost
+                ),
+                compiler::compile(&boost, "boost"),
+            ]
+            .join("\n")
+        }
+        // Without an input there is nothing to compile, and `run_boost` always interprets.
+        Err(_) => {
+            println!("cargo:warning=no input for 2019 day 9, so BOOST will be interpreted");
+            for path in resolver.candidates(2019, 9) {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+            STUB.to_string()
+        }
+    };
+    let source = [
+        boost,
+        compiler::compile(programs::QUINE, "quine"),
+        compiler::compile(programs::PATCHED, "patched"),
+        compiler::compile(programs::GUARDED, "guarded"),
+    ]
+    .join("\n");
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("compiled.rs");
+    fs::write(out, source)?;
+    Ok(())
+}
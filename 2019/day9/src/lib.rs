@@ -0,0 +1,157 @@
+use aoc::intcode::{self, heatmap::Heatmap, Computer, State};
+use aoc::Solution;
+use crossbeam::crossbeam_channel::unbounded;
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+mod programs;
+
+/// Native versions of the puzzle input and test programs, generated by `build.rs`.
+mod compiled {
+    include!(concat!(env!("OUT_DIR"), "/compiled.rs"));
+}
+
+type Program = fn(&mut Computer) -> State;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<i64>;
+
+    fn parse(&self, input: &str) -> aoc::Result<Vec<i64>> {
+        intcode::parse_program(input)
+    }
+
+    fn part1(&self, initial_state: &Vec<i64>) -> aoc::Result<aoc::Answer> {
+        last_output(run_boost(initial_state, 1)?)
+    }
+
+    fn part2(&self, initial_state: &Vec<i64>) -> aoc::Result<aoc::Answer> {
+        last_output(run_boost(initial_state, 2)?)
+    }
+}
+
+fn last_output(output: Vec<i64>) -> aoc::Result<aoc::Answer> {
+    output
+        .last()
+        .map(|&v| v.into())
+        .ok_or_else(|| aoc::Error::ExecError("the BOOST program produced no output".to_string()))
+}
+
+/// Runs a BOOST program, natively if it is the one compiled into this crate.
+pub fn run_boost(initial_state: &[i64], input: i64) -> aoc::Result<Vec<i64>> {
+    if initial_state == compiled::BOOST {
+        run_compiled(initial_state, compiled::boost, Some(input))
+    } else {
+        run_computer(initial_state, Some(input))
+    }
+}
+
+pub fn trace_memory(initial_state: &[i64], input: i64) -> aoc::Result<Heatmap> {
+    let (tx, rx) = unbounded();
+    let (mut comp, _output) = intcode::new(initial_state, rx);
+    let heatmap = Arc::new(Mutex::new(Heatmap::new()));
+    comp.attach_probe(heatmap.clone());
+
+    tx.send(input).unwrap();
+    drop(tx);
+    let state = comp.run_with_budget(intcode::Budget::unlimited());
+    drop(comp);
+    halted(state)?;
+
+    Ok(Arc::try_unwrap(heatmap).unwrap().into_inner().unwrap())
+}
+
+/// Runs the interpreter, the reference the compiled code is checked against.
+pub fn run_computer(initial_state: &[i64], input: Option<i64>) -> aoc::Result<Vec<i64>> {
+    run_compiled(
+        initial_state,
+        |comp| comp.run_with_budget(intcode::Budget::unlimited()),
+        input,
+    )
+}
+
+/// Runs `program` on a computer loaded with `initial_state`, which must be the program it was
+/// compiled from. Asking for more input than `input` stops it with `State::InputExhausted`.
+fn run_compiled(
+    initial_state: &[i64],
+    program: Program,
+    input: Option<i64>,
+) -> aoc::Result<Vec<i64>> {
+    let (tx, rx) = unbounded();
+    let (mut comp, output) = intcode::new(initial_state, rx);
+    input.iter().for_each(|v| tx.send(*v).unwrap());
+    drop(tx);
+
+    // Output is unbounded, so the program can run to the end before any of it is read.
+    halted(program(&mut comp))?;
+    drop(comp);
+    Ok(output.iter().collect())
+}
+
+fn halted(state: State) -> aoc::Result<()> {
+    match state {
+        State::Halted(_) => Ok(()),
+        State::BadOpCode(code) => Err(aoc::Error::BadOpCode(code)),
+        state => Err(aoc::Error::ExecError(format!(
+            "computer stopped without halting: {:?}",
+            state
+        ))),
+    }
+}
+
+#[test]
+fn test_case1() {
+    let initial_state = vec![
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+
+    let output = run_computer(&initial_state, None).unwrap();
+    assert_eq!(initial_state, output);
+}
+
+#[test]
+fn test_case2() {
+    let initial_state = vec![104, 1125899906842624, 99];
+
+    let output = run_computer(&initial_state, None).unwrap();
+    assert_eq!(vec![1125899906842624], output);
+}
+
+#[test]
+fn test_compiled_matches_interpreter() {
+    use programs::{GUARDED, PATCHED, QUINE};
+
+    // Without an input, `build.rs` compiles no BOOST program to check.
+    let boost = compiled::BOOST;
+    for input in (1..=2).filter(|_| !boost.is_empty()) {
+        assert_eq!(
+            run_compiled(boost, compiled::boost, Some(input)).unwrap(),
+            run_computer(boost, Some(input)).unwrap()
+        );
+    }
+
+    let cases: [(&[i64], Program); 3] = [
+        (QUINE, compiled::quine),
+        (PATCHED, compiled::patched),
+        (GUARDED, compiled::guarded),
+    ];
+    for (initial_state, program) in cases.iter() {
+        assert_eq!(
+            run_compiled(initial_state, *program, None).unwrap(),
+            run_computer(initial_state, None).unwrap()
+        );
+    }
+    assert_eq!(
+        run_compiled(GUARDED, compiled::guarded, None).unwrap(),
+        vec![42]
+    );
+}
+
+#[test]
+fn test_stopped_early() {
+    // Echoes two inputs, but is only given one.
+    let echo_twice = vec![3, 9, 4, 9, 3, 9, 4, 9, 99, 0];
+    assert!(run_computer(&echo_twice, Some(5)).is_err());
+    assert!(run_computer(&[104, 1, 42], None).is_err());
+}
@@ -1,45 +1,31 @@
-use aoc;
-use crossbeam::crossbeam_channel::unbounded;
-use std::thread;
-
-mod computer;
+use aoc::Solution;
+use day9::Day9;
+use std::env;
+use std::fs::File;
+use std::io::BufWriter;
 
 fn main() -> aoc::Result<()> {
-    let initial_state = computer::load_input("input")?;
-    let boost = run_computer(&initial_state, Some(1));
+    let initial_state = Day9.parse(&aoc::input::read(2019, 9)?)?;
+    let boost = day9::run_boost(&initial_state, 1)?;
     println!("BOOST key code: {:?}", boost);
 
-    let coords = run_computer(&initial_state, Some(2));
+    let coords = day9::run_boost(&initial_state, 2)?;
     println!("Coordinates: {:?}", coords);
 
-    Ok(())
-}
-
-fn run_computer(initial_state: &Vec<i64>, input: Option<i64>) -> Vec<i64> {
-    let (tx, rx) = unbounded();
-    let (mut comp, output) = computer::new(initial_state, rx.clone());
-    thread::spawn(move || {
-        comp.run();
-    });
-
-    input.iter().for_each(|v| tx.send(*v).unwrap());
-    output.iter().collect()
-}
+    // `day9 --heatmap boost.ppm` renders the memory activity of the BOOST test run, as a PNG
+    // instead if the path ends in `.png`.
+    let args: Vec<String> = env::args().collect();
+    if let [_, flag, path] = args.as_slice() {
+        if flag == "--heatmap" {
+            let heatmap = day9::trace_memory(&initial_state, 1)?;
+            let mut out = BufWriter::new(File::create(path)?);
+            if path.ends_with(".png") {
+                heatmap.total().write_png(&mut out, 64)?;
+            } else {
+                heatmap.total().write_ppm(&mut out, 64)?;
+            }
+        }
+    }
 
-#[test]
-fn test_case1() {
-    let initial_state = vec![
-        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
-    ];
-
-    let output = run_computer(&initial_state, None);
-    assert_eq!(initial_state, output);
-}
-
-#[test]
-fn test_case2() {
-    let initial_state = vec![104, 1125899906842624, 99];
-
-    let output = run_computer(&initial_state, None);
-    assert_eq!(vec![1125899906842624], output);
+    Ok(())
 }
@@ -0,0 +1,13 @@
+//! Programs compiled by the build script for tests, alongside the puzzle input.
+
+/// Outputs a copy of itself.
+pub const QUINE: &[i64] = &[
+    109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+];
+
+/// Overwrites its halt instruction at a fixed address with an output instruction.
+pub const PATCHED: &[i64] = &[1101, 104, 0, 4, 99, 42, 99];
+
+/// Does the same as `PATCHED` through the relative base, so the compiled code has to notice
+/// the write at run time.
+pub const GUARDED: &[i64] = &[109, 9, 21101, 104, 0, 0, 1105, 1, 9, 99, 42, 99];
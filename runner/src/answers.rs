@@ -0,0 +1,110 @@
+use aoc::{Answer, Error, Part};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use toml::Value;
+
+/// Known-correct answers for one year, as kept in `<year>/answers.toml`:
+///
+/// ```toml
+/// [day1]
+/// part1 = 3152919
+/// part2 = "fvstwblgqkhpuixdrnevmaycd"
+/// ```
+///
+/// Integers are numeric answers and strings are text answers. The answers for another account's
+/// inputs are kept next to it, in `<year>/answers-<account>.toml`.
+#[derive(Debug, Default)]
+pub struct Answers {
+    known: BTreeMap<(u8, Part), Answer>,
+}
+
+impl Answers {
+    /// Reads the answers file for `year` and `account` under `root`. A year without the file has
+    /// no known answers.
+    pub fn load(root: &Path, year: u16, account: Option<&str>) -> aoc::Result<Answers> {
+        let file = match account {
+            Some(account) => format!("answers-{}.toml", account),
+            None => "answers.toml".to_string(),
+        };
+        let path = root.join(year.to_string()).join(file);
+        match fs::read_to_string(&path) {
+            Ok(text) => Answers::parse(&text)
+                .map_err(|err| Error::BadAnswers(format!("{}: {}", path.display(), err))),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn parse(text: &str) -> aoc::Result<Answers> {
+        let bad = |msg: String| Error::BadAnswers(msg);
+        let table: BTreeMap<String, BTreeMap<String, Value>> =
+            toml::from_str(text).map_err(|err| bad(err.to_string()))?;
+
+        let mut known = BTreeMap::new();
+        for (day_key, parts) in table {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| bad(format!("expected [dayN], found [{}]", day_key)))?;
+
+            for (part_key, value) in parts {
+                let part = part_key
+                    .strip_prefix("part")
+                    .and_then(|part| part.parse().ok())
+                    .ok_or_else(|| bad(format!("[{}]: unknown key {}", day_key, part_key)))?;
+                let answer = match value {
+                    Value::Integer(n) => Answer::Number(n),
+                    Value::String(text) => Answer::Text(text),
+                    other => {
+                        return Err(bad(format!(
+                            "[{}] {}: expected an integer or a string, found {}",
+                            day_key, part_key, other
+                        )))
+                    }
+                };
+                known.insert((day, part), answer);
+            }
+        }
+
+        Ok(Answers { known })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&Answer> {
+        self.known.get(&(day, part))
+    }
+}
+
+#[test]
+fn test_parse_answers() {
+    let answers = Answers::parse(
+        r#"
+[day1]
+part1 = 3152919
+part2 = "fvstwblgqkhpuixdrnevmaycd"
+
+[day8]
+part2 = """
+#  #
+####
+"""
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(answers.get(1, Part::One), Some(&Answer::Number(3152919)));
+    assert_eq!(
+        answers.get(1, Part::Two),
+        Some(&Answer::from("fvstwblgqkhpuixdrnevmaycd"))
+    );
+    assert_eq!(
+        answers.get(8, Part::Two),
+        Some(&Answer::from("#  #\n####\n"))
+    );
+    assert_eq!(answers.get(8, Part::One), None);
+
+    assert!(Answers::parse("[first]\npart1 = 1").is_err());
+    assert!(Answers::parse("[day1]\npart3 = 1").is_err());
+    assert!(Answers::parse("[day1]\npart1 = 1.5").is_err());
+}
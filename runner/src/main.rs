@@ -0,0 +1,443 @@
+use answers::Answers;
+use aoc::input::{self, Resolver};
+use aoc::{Answer, Error, Part};
+use registry::Puzzle;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+use timing::{Stage, Timings};
+
+mod answers;
+mod registry;
+mod timing;
+
+const USAGE: &str =
+    "usage: aoc run|verify [YEAR [DAY]] [--part 1|2] [--input PATH] [--account NAME] [--save] \
+     [--bench N] [--json PATH]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Command {
+    /// Print the answers and how long they took.
+    #[default]
+    Run,
+    /// Check the answers against the known ones in `<year>/answers.toml`.
+    Verify,
+}
+
+/// What to run, as given on the command line.
+#[derive(Debug, Default)]
+struct Args {
+    command: Command,
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<Part>,
+    input: Option<PathBuf>,
+    account: Option<String>,
+    save: bool,
+    bench: Option<usize>,
+    json: Option<PathBuf>,
+}
+
+impl Args {
+    fn parse(args: &[String]) -> aoc::Result<Args> {
+        let usage = || Error::Usage(USAGE.to_string());
+
+        let command = match args.first().map(String::as_str) {
+            Some("run") => Command::Run,
+            Some("verify") => Command::Verify,
+            _ => return Err(usage()),
+        };
+
+        let mut parsed = Args {
+            command,
+            ..Args::default()
+        };
+        let mut positional = Vec::new();
+        let mut rest = args[1..].iter();
+        while let Some(arg) = rest.next() {
+            match arg.as_str() {
+                "--part" => parsed.part = Some(rest.next().ok_or_else(usage)?.parse()?),
+                "--input" => parsed.input = Some(rest.next().ok_or_else(usage)?.into()),
+                "--account" => parsed.account = Some(rest.next().ok_or_else(usage)?.clone()),
+                "--save" => parsed.save = true,
+                "--bench" => match rest.next().ok_or_else(usage)?.parse()? {
+                    0 => return Err(Error::Usage("--bench needs at least one run".to_string())),
+                    runs => parsed.bench = Some(runs),
+                },
+                "--json" => parsed.json = Some(rest.next().ok_or_else(usage)?.into()),
+                _ => positional.push(arg),
+            }
+        }
+
+        match positional.as_slice() {
+            [] => (),
+            [year] => parsed.year = Some(year.parse()?),
+            [year, day] => {
+                parsed.year = Some(year.parse()?);
+                parsed.day = Some(day.parse()?);
+            }
+            _ => return Err(usage()),
+        }
+
+        if parsed.input.is_some() && parsed.day.is_none() {
+            return Err(Error::Usage(
+                "--input needs a single year and day".to_string(),
+            ));
+        }
+
+        Ok(parsed)
+    }
+
+    fn selects(&self, puzzle: &Puzzle) -> bool {
+        self.year.is_none_or(|year| year == puzzle.year)
+            && self.day.is_none_or(|day| day == puzzle.day)
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::both().to_vec(),
+        }
+    }
+
+    /// Finds inputs as configured by the environment, overridden by `--input` and `--account`.
+    fn resolver(&self) -> aoc::Result<Resolver> {
+        self.configure(Resolver::from_env())
+    }
+
+    fn configure(&self, mut resolver: Resolver) -> aoc::Result<Resolver> {
+        if self.day.is_none() && self.input.is_none() && resolver.explicit_path().is_some() {
+            return Err(Error::Usage(
+                "$AOC_INPUT names a single input, so it needs a single year and day".to_string(),
+            ));
+        }
+        if let Some(path) = &self.input {
+            resolver = resolver.path(path);
+        }
+        if let Some(account) = &self.account {
+            resolver = resolver.account(account);
+        }
+        Ok(resolver)
+    }
+}
+
+/// Runs the requested parts of one puzzle, printing each answer and how long it took, or with
+/// `verify`, whether it matches the known answer. With `--bench`, parsing and every part are
+/// repeated and the spread of the times is printed as well. Returns the timings, and whether
+/// every part produced a (correct) answer.
+fn run(puzzle: &Puzzle, args: &Args, resolver: &Resolver, known: &Answers) -> (Timings, bool) {
+    let label = format!("{} day {}", puzzle.year, puzzle.day);
+    let mut timings = Timings::new(puzzle.year, puzzle.day);
+
+    let path = match resolver.resolve(puzzle.year, puzzle.day) {
+        Ok(path) => path,
+        Err(err) => {
+            println!("{}: {}", label, err);
+            return (timings, false);
+        }
+    };
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) => {
+            println!("{}: {}: {}", label, path.display(), err);
+            return (timings, false);
+        }
+    };
+    if args.save {
+        match resolver.store(puzzle.year, puzzle.day, &text) {
+            Ok(saved) if saved != path => println!("{}: saved input to {}", label, saved.display()),
+            Ok(_) => (),
+            Err(err) => {
+                println!("{}: {}", label, err);
+                return (timings, false);
+            }
+        }
+    }
+
+    let mut ok = true;
+    for round in 0..args.bench.unwrap_or(1) {
+        let (input, time) = timing::time(|| puzzle.solution.parse(&text));
+        let input = match input {
+            Ok(input) => input,
+            Err(err) => {
+                match err.in_file(&path) {
+                    err @ Error::Parse(_) => println!("{}: {}", label, err),
+                    err => println!("{}: {}: {}", label, path.display(), err),
+                }
+                return (timings, false);
+            }
+        };
+        timings.record(Stage::Parse, time);
+
+        for part in args.parts() {
+            let (answer, time) = timing::time(|| puzzle.solution.solve(input.as_ref(), part));
+            let solved = answer.is_ok();
+            if round == 0 {
+                ok &= match args.command {
+                    Command::Run => {
+                        report(&label, part, answer, args.bench.is_none().then_some(time))
+                    }
+                    Command::Verify => {
+                        let verdict = check(&answer, known.get(puzzle.day, part));
+                        print_verdict(&label, part, &answer, &verdict);
+                        verdict.passed()
+                    }
+                };
+            }
+            if solved {
+                timings.record(Stage::Solve(part), time);
+            }
+        }
+    }
+
+    if args.bench.is_some() {
+        for (stage, samples) in timings.stages() {
+            println!(
+                "{} {}: min {:.2?}, median {:.2?}, p95 {:.2?} over {} runs",
+                label,
+                stage,
+                samples.min(),
+                samples.median(),
+                samples.p95(),
+                samples.len()
+            );
+        }
+    }
+
+    (timings, ok)
+}
+
+/// Prints one answer, followed by its time if given. Returns false if the part failed.
+fn report(label: &str, part: Part, answer: aoc::Result<Answer>, time: Option<Duration>) -> bool {
+    let time = time.map(|t| format!(" ({:.2?})", t)).unwrap_or_default();
+    match answer {
+        Ok(Answer::Text(answer)) if answer.contains('\n') => {
+            println!(
+                "{} part {}{}:\n{}",
+                label,
+                part,
+                time,
+                answer.trim_matches('\n')
+            )
+        }
+        Ok(answer) => println!("{} part {}: {}{}", label, part, answer, time),
+        Err(Error::Unsolved) => println!("{} part {}: not solved yet", label, part),
+        Err(err) => {
+            println!("{} part {}: {}", label, part, err);
+            return false;
+        }
+    }
+    true
+}
+
+/// How an answer compares with the known one.
+#[derive(Debug, PartialEq, Eq)]
+enum Verdict<'a> {
+    Correct,
+    Wrong(&'a Answer),
+    /// Solved, but there is no known answer to compare with.
+    Unknown,
+    /// Not solved yet, and there is no known answer either.
+    Unsolved,
+    Failed,
+}
+
+impl Verdict<'_> {
+    fn passed(&self) -> bool {
+        match self {
+            Verdict::Correct | Verdict::Unknown | Verdict::Unsolved => true,
+            Verdict::Wrong(_) | Verdict::Failed => false,
+        }
+    }
+}
+
+fn check<'a>(answer: &aoc::Result<Answer>, known: Option<&'a Answer>) -> Verdict<'a> {
+    match (answer, known) {
+        (Ok(answer), Some(known)) if answer == known => Verdict::Correct,
+        (Ok(_), Some(known)) => Verdict::Wrong(known),
+        (Ok(_), None) => Verdict::Unknown,
+        (Err(Error::Unsolved), None) => Verdict::Unsolved,
+        (Err(_), _) => Verdict::Failed,
+    }
+}
+
+fn print_verdict(label: &str, part: Part, answer: &aoc::Result<Answer>, verdict: &Verdict) {
+    let show = |answer: &Answer| match answer {
+        Answer::Text(text) if text.contains('\n') => format!("\n{}\n", text.trim_matches('\n')),
+        answer => answer.to_string(),
+    };
+
+    match (answer, verdict) {
+        (_, Verdict::Correct) => println!("{} part {}: ok", label, part),
+        (Ok(answer), Verdict::Wrong(known)) => println!(
+            "{} part {}: WRONG: expected {}, got {}",
+            label,
+            part,
+            show(known),
+            show(answer)
+        ),
+        (Ok(answer), _) => println!(
+            "{} part {}: no known answer, got {}",
+            label,
+            part,
+            show(answer)
+        ),
+        (Err(Error::Unsolved), Verdict::Unsolved) => {
+            println!("{} part {}: not solved yet", label, part)
+        }
+        (Err(err), _) => println!("{} part {}: FAILED: {}", label, part, err),
+    }
+}
+
+fn main() {
+    if let Err(err) = try_main() {
+        eprintln!("{}", err);
+        process::exit(2);
+    }
+}
+
+fn try_main() -> aoc::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = Args::parse(&args)?;
+
+    let puzzles: Vec<Puzzle> = registry::puzzles()
+        .into_iter()
+        .filter(|p| args.selects(p))
+        .collect();
+    if puzzles.is_empty() {
+        return Err(Error::Usage("no solution for that puzzle".to_string()));
+    }
+
+    let resolver = args.resolver()?;
+    let mut known = BTreeMap::new();
+    for puzzle in &puzzles {
+        if let Entry::Vacant(entry) = known.entry(puzzle.year) {
+            entry.insert(Answers::load(
+                &input::repo_root(),
+                puzzle.year,
+                resolver.account_name(),
+            )?);
+        }
+    }
+
+    let mut failures = 0;
+    let mut timings = Vec::new();
+    for puzzle in &puzzles {
+        let (t, ok) = run(puzzle, &args, &resolver, &known[&puzzle.year]);
+        timings.push(t);
+        if !ok {
+            failures += 1;
+        }
+    }
+
+    if args.command == Command::Run && puzzles.len() > 1 {
+        print!("\n{}", timing::summary(&timings));
+    }
+    if let Some(path) = &args.json {
+        timing::write_json(fs::File::create(path)?, &timings)?;
+    }
+
+    if failures > 0 {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_args() {
+    let args = |s: &str| -> Vec<String> { s.split_whitespace().map(String::from).collect() };
+
+    let parsed = Args::parse(&args("run 2019 7 --part 2 --input in.txt")).unwrap();
+    assert_eq!(parsed.year, Some(2019));
+    assert_eq!(parsed.day, Some(7));
+    assert_eq!(parsed.part, Some(Part::Two));
+    assert_eq!(parsed.input, Some(PathBuf::from("in.txt")));
+    assert!(parsed.bench.is_none());
+
+    assert!(parsed.account.is_none() && !parsed.save);
+
+    let parsed = Args::parse(&args("verify 2019 7 --account bob --save")).unwrap();
+    assert_eq!(parsed.account.as_deref(), Some("bob"));
+    assert!(parsed.save);
+
+    let parsed = Args::parse(&args("run 2019 --bench 10 --json times.json")).unwrap();
+    assert_eq!(parsed.bench, Some(10));
+    assert_eq!(parsed.json, Some(PathBuf::from("times.json")));
+
+    assert_eq!(parsed.command, Command::Run);
+
+    let parsed = Args::parse(&args("verify 2018")).unwrap();
+    assert_eq!(parsed.command, Command::Verify);
+    assert_eq!(parsed.parts(), vec![Part::One, Part::Two]);
+    assert!(Args::parse(&args("run")).unwrap().year.is_none());
+
+    assert!(Args::parse(&args("walk 2019")).is_err());
+    assert!(Args::parse(&args("run 2019 --input in.txt")).is_err());
+    assert!(Args::parse(&args("run 2019 7 --part 3")).is_err());
+    assert!(Args::parse(&args("run 2019 --bench 0")).is_err());
+    assert!(Args::parse(&args("run 2019 --bench")).is_err());
+
+    // A path from the environment is held to the same rule as `--input`.
+    let from_env = || Resolver::new("/repo").path("in.txt");
+    let parsed = Args::parse(&args("run 2019")).unwrap();
+    assert!(parsed.configure(from_env()).is_err());
+    assert!(parsed.configure(Resolver::new("/repo")).is_ok());
+    let parsed = Args::parse(&args("run 2019 7")).unwrap();
+    let resolver = parsed.configure(from_env()).unwrap();
+    assert_eq!(
+        resolver.explicit_path(),
+        Some(std::path::Path::new("in.txt"))
+    );
+}
+
+/// Every answer in the `answers.toml` files must still come out the same.
+#[test]
+fn test_known_answers() {
+    let root = input::repo_root();
+    let resolver = Resolver::new(&root);
+    let mut known = BTreeMap::new();
+    for puzzle in registry::puzzles() {
+        let answers = known
+            .entry(puzzle.year)
+            .or_insert_with(|| Answers::load(&root, puzzle.year, None).unwrap());
+
+        let text = resolver.read(puzzle.year, puzzle.day).unwrap();
+        let input = puzzle.solution.parse(&text).unwrap();
+        for part in Part::both().iter() {
+            let answer = puzzle.solution.solve(input.as_ref(), *part);
+            let verdict = check(&answer, answers.get(puzzle.day, *part));
+            assert!(
+                verdict.passed(),
+                "{} day {} part {}: {:?}, got {:?}",
+                puzzle.year,
+                puzzle.day,
+                part,
+                verdict,
+                answer
+            );
+        }
+    }
+}
+
+#[test]
+fn test_check() {
+    let known = Answer::Number(42);
+    assert_eq!(check(&Ok(42.into()), Some(&known)), Verdict::Correct);
+    assert_eq!(check(&Ok(41.into()), Some(&known)), Verdict::Wrong(&known));
+    assert_eq!(
+        check(&Ok("42".into()), Some(&known)),
+        Verdict::Wrong(&known)
+    );
+    assert_eq!(check(&Ok(41.into()), None), Verdict::Unknown);
+    assert_eq!(check(&Err(Error::Unsolved), None), Verdict::Unsolved);
+    assert_eq!(check(&Err(Error::Unsolved), Some(&known)), Verdict::Failed);
+    assert_eq!(
+        check(&Err(Error::ExecError("boom".to_string())), None),
+        Verdict::Failed
+    );
+}
@@ -0,0 +1,40 @@
+use aoc::solution::AnySolution;
+
+/// A solution and the puzzle it solves.
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    pub solution: &'static dyn AnySolution,
+}
+
+impl Puzzle {
+    fn new(year: u16, day: u8, solution: &'static dyn AnySolution) -> Puzzle {
+        Puzzle {
+            year,
+            day,
+            solution,
+        }
+    }
+}
+
+/// Every known solution, ordered by year and day.
+pub fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::new(2018, 1, &aoc2018_day1::Day1),
+        Puzzle::new(2018, 2, &aoc2018_day2::Day2),
+        Puzzle::new(2018, 3, &aoc2018_day3::Day3),
+        Puzzle::new(2018, 4, &aoc2018_day4::Day4),
+        Puzzle::new(2018, 5, &aoc2018_day5::Day5),
+        Puzzle::new(2018, 6, &aoc2018_day6::Day6),
+        Puzzle::new(2019, 1, &aoc2019_day1::Day1),
+        Puzzle::new(2019, 2, &aoc2019_day2::Day2),
+        Puzzle::new(2019, 3, &aoc2019_day3::Day3),
+        Puzzle::new(2019, 4, &aoc2019_day4::Day4),
+        Puzzle::new(2019, 5, &aoc2019_day5::Day5),
+        Puzzle::new(2019, 6, &aoc2019_day6::Day6),
+        Puzzle::new(2019, 7, &aoc2019_day7::Day7),
+        Puzzle::new(2019, 8, &aoc2019_day8::Day8),
+        Puzzle::new(2019, 9, &aoc2019_day9::Day9),
+        Puzzle::new(2019, 10, &aoc2019_day10::Day10),
+    ]
+}
@@ -0,0 +1,221 @@
+use aoc::Part;
+use serde::Serialize;
+use std::fmt::{self, Write as FmtWrite};
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Stage {
+    fn all() -> [Stage; 3] {
+        [
+            Stage::Parse,
+            Stage::Solve(Part::One),
+            Stage::Solve(Part::Two),
+        ]
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => f.write_str("parse"),
+            Stage::Solve(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+/// Runs `f`, returning its result and how long it took.
+pub fn time<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Wall-clock times of repeated runs of one stage.
+#[derive(Debug, Clone, Default)]
+pub struct Samples {
+    times: Vec<Duration>,
+}
+
+impl Samples {
+    pub fn push(&mut self, time: Duration) {
+        self.times.push(time);
+    }
+
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.percentile(0)
+    }
+
+    pub fn median(&self) -> Duration {
+        self.percentile(50)
+    }
+
+    pub fn p95(&self) -> Duration {
+        self.percentile(95)
+    }
+
+    /// Nearest-rank percentile, so every reported time is one that was actually measured.
+    fn percentile(&self, p: usize) -> Duration {
+        let mut sorted = self.times.clone();
+        sorted.sort();
+        let rank = (p * sorted.len()).div_ceil(100).max(1);
+        sorted.get(rank - 1).copied().unwrap_or_default()
+    }
+}
+
+/// Everything measured while running one puzzle.
+#[derive(Debug, Clone)]
+pub struct Timings {
+    pub year: u16,
+    pub day: u8,
+    stages: Vec<(Stage, Samples)>,
+}
+
+impl Timings {
+    pub fn new(year: u16, day: u8) -> Timings {
+        Timings {
+            year,
+            day,
+            stages: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, stage: Stage, time: Duration) {
+        match self.stages.iter_mut().find(|(s, _)| *s == stage) {
+            Some((_, samples)) => samples.push(time),
+            None => {
+                let mut samples = Samples::default();
+                samples.push(time);
+                self.stages.push((stage, samples));
+            }
+        }
+    }
+
+    pub fn get(&self, stage: Stage) -> Option<&Samples> {
+        self.stages
+            .iter()
+            .find(|(s, _)| *s == stage)
+            .map(|(_, samples)| samples)
+    }
+
+    pub fn stages(&self) -> impl Iterator<Item = &(Stage, Samples)> {
+        self.stages.iter()
+    }
+}
+
+#[derive(Serialize)]
+struct Record {
+    year: u16,
+    day: u8,
+    stage: String,
+    runs: usize,
+    min_ns: u128,
+    median_ns: u128,
+    p95_ns: u128,
+}
+
+/// Writes one JSON record per puzzle and stage, with times in nanoseconds.
+pub fn write_json<W: Write>(out: W, timings: &[Timings]) -> aoc::Result<()> {
+    let records: Vec<Record> = timings
+        .iter()
+        .flat_map(|t| {
+            t.stages().map(move |(stage, samples)| Record {
+                year: t.year,
+                day: t.day,
+                stage: stage.to_string(),
+                runs: samples.len(),
+                min_ns: samples.min().as_nanos(),
+                median_ns: samples.median().as_nanos(),
+                p95_ns: samples.p95().as_nanos(),
+            })
+        })
+        .collect();
+
+    serde_json::to_writer_pretty(out, &records).map_err(std::io::Error::from)?;
+    Ok(())
+}
+
+/// A table per year of the median time of every stage, with a total per day and per stage.
+pub fn summary(timings: &[Timings]) -> String {
+    let mut years: Vec<u16> = timings.iter().map(|t| t.year).collect();
+    years.dedup();
+
+    let mut s = String::new();
+    for year in years {
+        write!(s, "{:<8}", year).unwrap();
+        for stage in Stage::all().iter() {
+            write!(s, "{:>12}", stage.to_string()).unwrap();
+        }
+        writeln!(s, "{:>12}", "total").unwrap();
+
+        let mut totals = [Duration::default(); 3];
+        for t in timings.iter().filter(|t| t.year == year) {
+            write!(s, "{:<8}", format!("day {}", t.day)).unwrap();
+            let mut day_total = Duration::default();
+            for (i, stage) in Stage::all().iter().enumerate() {
+                match t.get(*stage) {
+                    Some(samples) => {
+                        let median = samples.median();
+                        totals[i] += median;
+                        day_total += median;
+                        write!(s, "{:>12.2?}", median).unwrap();
+                    }
+                    None => write!(s, "{:>12}", "-").unwrap(),
+                }
+            }
+            writeln!(s, "{:>12.2?}", day_total).unwrap();
+        }
+
+        write!(s, "{:<8}", "total").unwrap();
+        for total in totals.iter() {
+            write!(s, "{:>12.2?}", total).unwrap();
+        }
+        writeln!(s, "{:>12.2?}\n", totals.iter().sum::<Duration>()).unwrap();
+    }
+
+    s
+}
+
+#[test]
+fn test_samples() {
+    let mut samples = Samples::default();
+    for ms in [5, 1, 4, 2, 3, 100, 6, 7, 8, 9].iter() {
+        samples.push(Duration::from_millis(*ms));
+    }
+
+    assert_eq!(samples.min(), Duration::from_millis(1));
+    assert_eq!(samples.median(), Duration::from_millis(5));
+    assert_eq!(samples.p95(), Duration::from_millis(100));
+    assert_eq!(Samples::default().median(), Duration::default());
+}
+
+#[test]
+fn test_summary_and_json() {
+    let mut timings = Timings::new(2019, 1);
+    timings.record(Stage::Parse, Duration::from_millis(1));
+    timings.record(Stage::Solve(Part::One), Duration::from_millis(2));
+    timings.record(Stage::Solve(Part::One), Duration::from_millis(4));
+
+    let table = summary(&[timings.clone()]);
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines[0].starts_with("2019"));
+    assert!(lines[1].starts_with("day 1"));
+    assert!(lines[1].contains("1.00ms") && lines[1].contains('-'));
+    assert!(lines[2].starts_with("total") && lines[2].contains("3.00ms"));
+
+    let mut json = Vec::new();
+    write_json(&mut json, &[timings]).unwrap();
+    let records: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(records[1]["stage"], "part 1");
+    assert_eq!(records[1]["runs"], 2);
+    assert_eq!(records[1]["min_ns"], 2_000_000);
+}